@@ -0,0 +1,64 @@
+//! Benchmarks parse, part 1 and part 2 of every registered day, on its real
+//! input and on its example, as `dayNN/<input|example>/<step>`. Variants of a
+//! part are benchmarked as `partN/<variant>`.
+//!
+//! Criterion keeps the last results in `target/criterion` and reports the change
+//! against them, so run `cargo bench -- --save-baseline <name>` on one commit and
+//! `cargo bench -- --baseline <name>` on another to compare the two.
+
+use std::fs;
+use std::hint::black_box;
+use std::time::Duration;
+
+use advent_of_code_template::etc::examples::Example;
+use advent_of_code_template::{get_day, Day};
+use criterion::{criterion_group, criterion_main, Criterion};
+
+fn bench_input(c: &mut Criterion, name: &str, day: &Day, part1: &str, part2: &str) {
+    let mut group = c.benchmark_group(name);
+    if let Some(parse) = day.parse {
+        group.bench_function("parse", |b| b.iter(|| parse(black_box(part1))));
+    }
+    for (part, input) in [(1, part1), (2, part2)] {
+        for variant in day.variants(part) {
+            let id = match variant.name {
+                "default" => format!("part{part}"),
+                name => format!("part{part}/{name}"),
+            };
+            group.bench_function(id, |b| b.iter(|| (variant.solve)(black_box(input))));
+        }
+    }
+    group.finish();
+}
+
+fn days(c: &mut Criterion) {
+    for number in 1..=25 {
+        let day = match get_day(number) {
+            Some(day) => day,
+            None => continue,
+        };
+        if let Ok(input) = fs::read_to_string(format!("input/day{number:02}.txt")) {
+            bench_input(c, &format!("day{number:02}/input"), &day, &input, &input);
+        }
+        if let Some(example) = Example::load(number) {
+            let part2 = example.part2.as_deref().unwrap_or(&example.part1);
+            bench_input(
+                c,
+                &format!("day{number:02}/example"),
+                &day,
+                &example.part1,
+                part2,
+            );
+        }
+    }
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default()
+        .sample_size(10)
+        .warm_up_time(Duration::from_millis(500))
+        .measurement_time(Duration::from_secs(2));
+    targets = days
+}
+criterion_main!(benches);
@@ -0,0 +1,43 @@
+/* C API of the Advent of Code 2022 solvers, built by `cargo build --release`
+ * as target/release/libadvent_of_code_template.{so,dylib} or
+ * advent_of_code_template.dll. */
+#ifndef AOC_H
+#define AOC_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+#define AOC_OK 0
+#define AOC_UNKNOWN_DAY 1
+#define AOC_INVALID_PART 2
+#define AOC_INVALID_INPUT 3
+#define AOC_PANIC 4
+#define AOC_UNSOLVED 5
+
+typedef struct {
+    /* One of the AOC_* codes. */
+    int32_t status;
+    /* The answer if status is AOC_OK, NULL otherwise. */
+    char *answer;
+    /* What went wrong if status is not AOC_OK, NULL otherwise. */
+    char *error;
+    /* Time spent in the solver. */
+    double elapsed_ms;
+} AocResult;
+
+/* Solves `part` (1 or 2) of `day` for the `len` bytes of UTF-8 input at `input`.
+ * `answer` and `error` of the result have to be released with aoc_free_string. */
+AocResult aoc_solve(uint8_t day, uint8_t part, const uint8_t *input, size_t len);
+
+/* Releases a string returned by aoc_solve. NULL is ignored. */
+void aoc_free_string(char *s);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* AOC_H */
@@ -1,8 +1,10 @@
+use crate::etc::trace;
 use crate::{Solution, SolutionPair};
 
 ///////////////////////////////////////////////////////////////////////////////
 
-fn parse(input: &str) -> Vec<u64> {
+pub fn parse(input: &str) -> Vec<u64> {
+    let _span = trace::span("parse");
     let mut elf_calory_map: Vec<u64> = Vec::new();
     let mut current = 0;
     for line in input.lines() {
@@ -32,11 +34,9 @@ pub fn solve_day2(input: &str) -> u64 {
     return top3;
 }
 
-pub fn solve() -> SolutionPair {
-    let input = include_str!("../../input/day01.txt");
-
-    let sol1: u64 = solve_day1(&input);
-    let sol2: u64 = solve_day2(&input);
+pub fn solve(input: &str) -> SolutionPair {
+    let sol1: u64 = solve_day1(input);
+    let sol2: u64 = solve_day2(input);
 
     (Solution::from(sol1), Solution::from(sol2))
 }
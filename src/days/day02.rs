@@ -32,7 +32,7 @@ fn parse_line(line: &str) -> u64 {
     return score.into();
 }
 
-fn solve_day1(input: &str) -> u64 {
+pub fn solve_day1(input: &str) -> u64 {
     let total_score = input.lines().map(parse_line).sum();
     return total_score;
 }
@@ -55,15 +55,14 @@ fn parse_line_2(line: &str) -> u64 {
     return score.into();
 }
 
-fn solve_day2(input: &str) -> u64 {
+pub fn solve_day2(input: &str) -> u64 {
     let total_score = input.lines().map(parse_line_2).sum();
     return total_score;
 }
 
-pub fn solve() -> SolutionPair {
-    let input = include_str!("../../input/day02.txt");
-    let sol1: u64 = solve_day1(&input);
-    let sol2: u64 = solve_day2(&input);
+pub fn solve(input: &str) -> SolutionPair {
+    let sol1: u64 = solve_day1(input);
+    let sol2: u64 = solve_day2(input);
 
     (Solution::from(sol1), Solution::from(sol2))
 }
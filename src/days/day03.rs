@@ -24,7 +24,7 @@ fn calc_value(val: &char) -> u64 {
     return val_as_uint;
 }
 
-fn solve_day1(input: &str) -> u64 {
+pub fn solve_day1(input: &str) -> u64 {
     let lines = input.lines();
     let mut total = 0;
     for line in lines {
@@ -43,7 +43,7 @@ fn solve_day1(input: &str) -> u64 {
     return total;
 }
 
-fn solve_day2(input: &str) -> u64 {
+pub fn solve_day2(input: &str) -> u64 {
     let mut total = 0;
     let line_sets: Vec<HashSet<char>> = input
         .lines()
@@ -65,10 +65,9 @@ fn solve_day2(input: &str) -> u64 {
     total
 }
 
-pub fn solve() -> SolutionPair {
-    let input = include_str!("../../input/day03.txt");
-    let sol1: u64 = solve_day1(&input);
-    let sol2: u64 = solve_day2(&input);
+pub fn solve(input: &str) -> SolutionPair {
+    let sol1: u64 = solve_day1(input);
+    let sol2: u64 = solve_day2(input);
 
     (Solution::from(sol1), Solution::from(sol2))
 }
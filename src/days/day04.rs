@@ -1,15 +1,13 @@
+use crate::etc::intervals::Interval;
 use crate::{Solution, SolutionPair};
-use std::collections::HashSet;
 ///////////////////////////////////////////////////////////////////////////////
 
-fn parse_range(range: &str) -> HashSet<u64> {
+fn parse_range(range: &str) -> Interval<u64> {
     let mut range_iter = range.split("-");
     let start: u64 = range_iter.next().unwrap().parse().unwrap();
     let stop: u64 = range_iter.next().unwrap().parse().unwrap();
 
-    let s = HashSet::from_iter(start..=stop);
-    //println!("{s:?}");
-    s
+    Interval::closed(start, stop)
 }
 
 fn parse_line(line: &str) -> u64 {
@@ -24,7 +22,7 @@ fn parse_line(line: &str) -> u64 {
     }
 }
 
-fn solve_day1(input: &str) -> u64 {
+pub fn solve_day1(input: &str) -> u64 {
     input.lines().map(parse_line).sum()
 }
 
@@ -33,21 +31,20 @@ fn parse_line_2(line: &str) -> u64 {
     let first = pair_iter.next().unwrap();
     let second = pair_iter.next().unwrap();
 
-    if first.is_disjoint(&second) {
-        0
-    } else {
+    if first.overlaps(&second) {
         1
+    } else {
+        0
     }
 }
 
-fn solve_day2(input: &str) -> u64 {
+pub fn solve_day2(input: &str) -> u64 {
     input.lines().map(parse_line_2).sum()
 }
 
-pub fn solve() -> SolutionPair {
-    let input = include_str!("../../input/day04.txt");
-    let sol1: u64 = solve_day1(&input);
-    let sol2: u64 = solve_day2(&input);
+pub fn solve(input: &str) -> SolutionPair {
+    let sol1: u64 = solve_day1(input);
+    let sol2: u64 = solve_day2(input);
 
     (Solution::from(sol1), Solution::from(sol2))
 }
@@ -55,7 +52,37 @@ pub fn solve() -> SolutionPair {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::etc::intervals::RangeSet;
     use indoc::indoc;
+    use proptest::prelude::*;
+
+    fn assignment() -> impl Strategy<Value = String> {
+        ((1..100u32, 1..100u32), (1..100u32, 1..100u32)).prop_map(|((a, b), (c, d))| {
+            format!("{}-{},{}-{}", a.min(b), a.max(b), c.min(d), c.max(d))
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn overlaps_include_containments(pairs in prop::collection::vec(assignment(), 0..50)) {
+            let input = pairs.join("\n");
+            prop_assert!(solve_day2(&input) >= solve_day1(&input));
+        }
+
+        // One assignment contains the other exactly when together they cover
+        // no more sections than the larger one, and they overlap exactly when
+        // they cover fewer than both.
+        #[test]
+        fn assignments_agree_with_range_sets(pair in assignment()) {
+            let (first, second) = pair.split_once(',').unwrap();
+            let (first, second) = (parse_range(first), parse_range(second));
+            let both = RangeSet::from_iter([first, second]);
+            let contained = both.len() == first.len().max(second.len());
+            prop_assert_eq!(parse_line(&pair), contained as u64);
+            let overlapping = both.len() < first.len() + second.len();
+            prop_assert_eq!(parse_line_2(&pair), overlapping as u64);
+        }
+    }
 
     #[test]
     fn sample_day_1() {
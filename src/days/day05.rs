@@ -10,7 +10,7 @@ fn move_re() -> &'static regex::Regex {
     })
 }
 
-fn solve_day1(input: &str) -> String {
+pub fn solve_day1(input: &str) -> String {
     let (start, instructions) = input.split_once("\n\n").unwrap();
 
     let mut parse_start_setup_iter = start.lines().rev();
@@ -61,7 +61,7 @@ fn solve_day1(input: &str) -> String {
     result
 }
 
-fn solve_day2(input: &str) -> String {
+pub fn solve_day2(input: &str) -> String {
     let (start, instructions) = input.split_once("\n\n").unwrap();
 
     let mut parse_start_setup_iter = start.lines().rev();
@@ -111,10 +111,9 @@ fn solve_day2(input: &str) -> String {
     // println!("{stacks:?}");
     result
 }
-pub fn solve() -> SolutionPair {
-    let input = include_str!("../../input/day05.txt");
-    let sol1: String = solve_day1(&input);
-    let sol2: String = solve_day2(&input);
+pub fn solve(input: &str) -> SolutionPair {
+    let sol1: String = solve_day1(input);
+    let sol2: String = solve_day2(input);
 
     (Solution::from(sol1), Solution::from(sol2))
 }
@@ -1,6 +1,19 @@
 use std::collections::HashSet;
 
-use crate::{Solution, SolutionPair};
+use crate::{Solution, SolutionPair, Variant};
+
+pub const VARIANTS: &[Variant] = &[
+    Variant {
+        name: "array",
+        part: 1,
+        solve: |input| Solution::from(array_solver(input, 4)),
+    },
+    Variant {
+        name: "array",
+        part: 2,
+        solve: |input| Solution::from(array_solver(input, 14)),
+    },
+];
 
 fn generic_solver(input: &str, window_size: usize) -> u64 {
     let windows = input.as_bytes().windows(window_size);
@@ -13,17 +26,42 @@ fn generic_solver(input: &str, window_size: usize) -> u64 {
     }
     unreachable!()
 }
-fn solve_day1(input: &str) -> u64 {
+
+/// Slides the window along while counting the letters in it, instead of
+/// collecting every window into a set.
+fn array_solver(input: &str, window_size: usize) -> u64 {
+    let bytes = input.as_bytes();
+    let mut counts = [0u16; 256];
+    let mut duplicates = 0;
+    for (i, &byte) in bytes.iter().enumerate() {
+        counts[byte as usize] += 1;
+        if counts[byte as usize] == 2 {
+            duplicates += 1;
+        }
+        if i >= window_size {
+            let left = bytes[i - window_size] as usize;
+            counts[left] -= 1;
+            if counts[left] == 1 {
+                duplicates -= 1;
+            }
+        }
+        if i + 1 >= window_size && duplicates == 0 {
+            return (i + 1) as u64;
+        }
+    }
+    unreachable!()
+}
+
+pub fn solve_day1(input: &str) -> u64 {
     generic_solver(input, 4)
 }
 
-fn solve_day2(input: &str) -> u64 {
+pub fn solve_day2(input: &str) -> u64 {
     generic_solver(input, 14)
 }
-pub fn solve() -> SolutionPair {
-    let input = include_str!("../../input/day06.txt");
-    let sol1 = solve_day1(&input);
-    let sol2 = solve_day2(&input);
+pub fn solve(input: &str) -> SolutionPair {
+    let sol1 = solve_day1(input);
+    let sol2 = solve_day2(input);
 
     (Solution::from(sol1), Solution::from(sol2))
 }
@@ -61,4 +99,19 @@ mod tests {
             assert_eq!(result, expected);
         }
     }
+
+    #[test]
+    fn array_variant() {
+        let inputs = vec![
+            ("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 7, 19),
+            ("bvwbjplbgvbhsrlpgdmjqwftvncz", 5, 23),
+            ("nppdvjthqldpwncqszvftbrmjlhg", 6, 23),
+            ("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 10, 29),
+            ("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 11, 26),
+        ];
+        for (input, expected_1, expected_2) in inputs {
+            assert_eq!(array_solver(input, 4), expected_1);
+            assert_eq!(array_solver(input, 14), expected_2);
+        }
+    }
 }
@@ -1,10 +1,11 @@
 use std::{cell::RefCell, fmt, rc::Rc};
 
+use crate::etc::trace;
 use crate::{Solution, SolutionPair};
 
 type Node = Rc<RefCell<Folder>>;
 
-struct Folder {
+pub struct Folder {
     name: String,
     parent: Option<Node>,
     children: Vec<Box<FolderChild>>,
@@ -99,7 +100,8 @@ impl fmt::Debug for FolderChild {
     }
 }
 
-fn parse(input: &str) -> Node {
+pub fn parse(input: &str) -> Node {
+    let _span = trace::span("parse");
     let lines = input.lines();
     let root = Folder::new("/", None);
     let mut current = root.clone();
@@ -142,7 +144,7 @@ fn parse(input: &str) -> Node {
     root
 }
 
-fn solve_day1(input: &str) -> u64 {
+pub fn solve_day1(input: &str) -> u64 {
     let out = parse(input);
     // println!("{out:?}");
     // println!("{}", out.borrow().size());
@@ -158,7 +160,7 @@ fn solve_day1(input: &str) -> u64 {
     correct
 }
 
-fn solve_day2(input: &str) -> u64 {
+pub fn solve_day2(input: &str) -> u64 {
     let out = parse(input);
     let total_size = 70_000_000u64;
     let currently_used = out.borrow().size();
@@ -178,10 +180,9 @@ fn solve_day2(input: &str) -> u64 {
     correct
 }
 
-pub fn solve() -> SolutionPair {
-    let input = include_str!("../../input/day07.txt");
-    let sol1 = solve_day1(&input);
-    let sol2 = solve_day2(&input);
+pub fn solve(input: &str) -> SolutionPair {
+    let sol1 = solve_day1(input);
+    let sol2 = solve_day2(input);
 
     (Solution::from(sol1), Solution::from(sol2))
 }
@@ -190,6 +191,56 @@ pub fn solve() -> SolutionPair {
 mod tests {
     use super::*;
     use indoc::indoc;
+    use proptest::prelude::*;
+
+    #[derive(Clone, Debug)]
+    struct Tree {
+        files: Vec<u64>,
+        dirs: Vec<Tree>,
+    }
+
+    impl Tree {
+        fn total(&self) -> u64 {
+            self.files.iter().sum::<u64>() + self.dirs.iter().map(Tree::total).sum::<u64>()
+        }
+
+        fn transcript(&self, out: &mut String) {
+            out.push_str("$ ls\n");
+            for i in 0..self.dirs.len() {
+                out.push_str(&format!("dir d{i}\n"));
+            }
+            for (i, size) in self.files.iter().enumerate() {
+                out.push_str(&format!("{size} f{i}.txt\n"));
+            }
+            for (i, dir) in self.dirs.iter().enumerate() {
+                out.push_str(&format!("$ cd d{i}\n"));
+                dir.transcript(out);
+                out.push_str("$ cd ..\n");
+            }
+        }
+    }
+
+    fn tree() -> impl Strategy<Value = Tree> {
+        let files = || prop::collection::vec(1..300_000u64, 0..4);
+        let leaf = files().prop_map(|files| Tree {
+            files,
+            dirs: vec![],
+        });
+        leaf.prop_recursive(4, 32, 4, move |inner| {
+            (files(), prop::collection::vec(inner, 0..4))
+                .prop_map(|(files, dirs)| Tree { files, dirs })
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn total_size_is_sum_of_files(tree in tree()) {
+            let mut input = "$ cd /\n".to_owned();
+            tree.transcript(&mut input);
+            let root = parse(&input);
+            prop_assert_eq!(root.borrow().size(), tree.total());
+        }
+    }
 
     #[test]
     fn sample_day_1() {
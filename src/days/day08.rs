@@ -1,14 +1,15 @@
+use crate::etc::grid::{Grid, ORTHOGONAL};
+use crate::etc::trace;
 use crate::{Solution, SolutionPair};
-type ParsedMap = Vec<Vec<(bool, u64)>>;
+pub type ParsedMap = Grid<(bool, u64)>;
 
-fn solve_day1(input: &ParsedMap) -> u64 {
-    let visibile_trees = input.iter().flatten().filter(|&&(e, _)| e).count();
+pub fn solve_day1(input: &ParsedMap) -> u64 {
+    let visibile_trees = input.values().filter(|&&(e, _)| e).count();
     visibile_trees as u64
 }
-fn solve_day2(input: &ParsedMap) -> u64 {
+pub fn solve_day2(input: &ParsedMap) -> u64 {
     let max_scenic_score = input
-        .iter()
-        .flatten()
+        .values()
         .map(|&(_, scenic_score)| scenic_score)
         .max()
         .unwrap_or(0);
@@ -38,52 +39,28 @@ where
         .all(|other_tree_size: &u32| other_tree_size < tree_size)
 }
 
-fn visibility_map(input: &Vec<Vec<u32>>) -> ParsedMap {
-    input
-        .iter()
-        .enumerate()
-        .map(|(row, rowdata)| {
-            rowdata
-                .iter()
-                .enumerate()
-                .map(|(column, tree_size)| {
-                    let up = (0..row).rev().map(|row| &input[row][column]);
-                    let up_visible = is_visible_calc(up.clone(), tree_size);
-                    let up_scenic = scenic_calc(up, tree_size);
-
-                    let down = ((row + 1)..input.len()).map(|row| &input[row][column]);
-                    let down_visible = is_visible_calc(down.clone(), tree_size);
-                    let down_scenic = scenic_calc(down, tree_size);
-
-                    let left = ((0..column).rev()).map(|column| &input[row][column]);
-                    let left_visible = is_visible_calc(left.clone(), tree_size);
-                    let left_scenic = scenic_calc(left, tree_size);
-
-                    let right = ((column + 1)..rowdata.len()).map(|column| &input[row][column]);
-                    let right_visible = is_visible_calc(right.clone(), tree_size);
-                    let right_scenic = scenic_calc(right, tree_size);
-
-                    let visible = up_visible || down_visible || left_visible || right_visible;
-                    let scenic = up_scenic * down_scenic * left_scenic * right_scenic;
+fn visibility_map(input: &Grid<u32>) -> ParsedMap {
+    input.map(|point, tree_size| {
+        let mut visible = false;
+        let mut scenic = 1;
+        // looking up, left, right and down from the tree
+        for direction in ORTHOGONAL {
+            let trees = input.ray(point, direction).map(|(_, other)| other);
+            visible |= is_visible_calc(trees.clone(), tree_size);
+            scenic *= scenic_calc(trees, tree_size);
+        }
 
-                    (visible, scenic as u64)
-                })
-                .collect()
-        })
-        .collect()
+        (visible, scenic as u64)
+    })
 }
 
-fn parse(input: &str) -> ParsedMap {
-    let rows = input.lines();
-    let result: Vec<Vec<u32>> = rows
-        .into_iter()
-        .map(|row| row.chars().map(|c| c.to_digit(10).unwrap()).collect())
-        .collect();
+pub fn parse(input: &str) -> ParsedMap {
+    let _span = trace::span("parse");
+    let result = Grid::parse(input, |c| c.to_digit(10).unwrap());
     visibility_map(&result)
 }
 
-pub fn solve() -> SolutionPair {
-    let input = include_str!("../../input/day08.txt");
+pub fn solve(input: &str) -> SolutionPair {
     let parsed_input = parse(input);
     let sol1 = solve_day1(&parsed_input);
     let sol2 = solve_day2(&parsed_input);
@@ -1,107 +1,66 @@
 use std::collections::HashSet;
 
+use crate::etc::geom::Point2;
 use crate::{Solution, SolutionPair};
 
-type Coords = (i32, i32);
+type Coords = Point2<i32>;
 
-fn hamil_dist(c1: &Coords, c2: &Coords) -> u32 {
-    let x_diff = c1.0.abs_diff(c2.0);
-    let y_diff = c1.1.abs_diff(c2.1);
-    x_diff.max(y_diff)
-}
-
-fn calculate_tail_pos(input: &str, tail_count: usize) -> u64 {
+/// Positions of the last knot after every step of the head.
+fn tail_path(input: &str, tail_count: usize) -> Vec<Coords> {
     let instructions = input.lines();
-    let mut knots: Vec<Coords> = [(0, 0)].into_iter().cycle().take(1 + tail_count).collect();
+    let mut knots: Vec<Coords> = vec![Coords::ORIGIN; 1 + tail_count];
     let last_knot_pos = knots.len() - 1;
     let mut last_knot_positions = vec![knots[last_knot_pos]];
     for inst in instructions {
         let to_move = inst[2..].parse::<i32>().unwrap();
+        let step = match &inst[..1] {
+            "R" => Coords::new(1, 0),
+            "U" => Coords::new(0, 1),
+            "L" => Coords::new(-1, 0),
+            "D" => Coords::new(0, -1),
+            _ => unreachable!(),
+        };
 
         for _ in 0..to_move {
-            // println!("{}: {:?}, {:?}, ", inst, head, tail);
             // move head
-
-            match inst {
-                _ if inst.starts_with("R") => knots[0].0 += 1,
-                _ if inst.starts_with("U") => knots[0].1 += 1,
-                _ if inst.starts_with("L") => knots[0].0 -= 1,
-                _ if inst.starts_with("D") => knots[0].1 -= 1,
-                _ => unreachable!(),
-            }
+            knots[0] += step;
 
             // move tails
             for i in 1..knots.len() {
                 let head = knots[i - 1];
-                let mut tail = &mut knots[i];
+                let tail = &mut knots[i];
                 //if distance is large enough
-                let dista = hamil_dist(&head, &tail);
-                if dista > 2 {
-                    panic!();
-                }
+                let dista = head.chebyshev(*tail);
+                assert!(dista <= 2, "{head:?} ran away from {tail:?}");
                 if dista > 1 {
-                    if head.0 == tail.0 {
-                        // move tail in y direction
-                        if head.1 > tail.1 {
-                            tail.1 += 1;
-                        } else {
-                            tail.1 -= 1;
-                        }
-                    } else if head.1 == tail.1 {
-                        // move tail in x direction
-                        if head.0 > tail.0 {
-                            tail.0 += 1;
-                        } else {
-                            tail.0 -= 1;
-                        }
-                    } else if head.0.abs_diff(tail.0) == 2 {
-                        if head.0 > tail.0 {
-                            tail.0 += 1;
-                        } else {
-                            tail.0 -= 1;
-                        }
-                        if head.1 > tail.1 {
-                            tail.1 += 1;
-                        } else {
-                            tail.1 -= 1;
-                        }
-                    } else if head.1.abs_diff(tail.1) == 2 {
-                        if head.1 > tail.1 {
-                            tail.1 += 1;
-                        } else {
-                            tail.1 -= 1;
-                        }
-                        if head.0 > tail.0 {
-                            tail.0 += 1;
-                        } else {
-                            tail.0 -= 1;
-                        }
-                    } else {
-                        unreachable!("{head:?} {tail:?} {dista}");
-                    }
+                    // one step towards the head, diagonally if not in line
+                    *tail += (head - *tail).signum();
                 }
             }
 
             last_knot_positions.push(knots[last_knot_pos]);
         }
     }
-    let tail_set: HashSet<Coords> = HashSet::from_iter(last_knot_positions.iter().cloned());
+    last_knot_positions
+}
+
+fn calculate_tail_pos(input: &str, tail_count: usize) -> u64 {
+    let tail_set: HashSet<Coords> = HashSet::from_iter(tail_path(input, tail_count));
     // println!("{tail_set:?}");
     tail_set.len() as u64
 }
 
-fn solve_day1(input: &str) -> u64 {
+pub fn solve_day1(input: &str) -> u64 {
     calculate_tail_pos(&input, 1)
 }
 
-fn solve_day2(input: &str) -> u64 {
+pub fn solve_day2(input: &str) -> u64 {
     calculate_tail_pos(&input, 9)
 }
 
-pub fn solve() -> SolutionPair {
-    let input = include_str!("../../input/day09.txt");
-    let sol1 = solve_day1(&input);
-    let sol2 = solve_day2(&input);
+pub fn solve(input: &str) -> SolutionPair {
+    let sol1 = solve_day1(input);
+    let sol2 = solve_day2(input);
 
     (Solution::from(sol1), Solution::from(sol2))
 }
@@ -110,6 +69,64 @@ pub fn solve() -> SolutionPair {
 mod tests {
     use super::*;
     use indoc::indoc;
+    use proptest::prelude::*;
+
+    fn motions() -> impl Strategy<Value = String> {
+        let motion = (prop::sample::select(vec!['R', 'U', 'L', 'D']), 1..10u32)
+            .prop_map(|(direction, steps)| format!("{direction} {steps}"));
+        prop::collection::vec(motion, 0..40).prop_map(|motions| motions.join("\n"))
+    }
+
+    proptest! {
+        // A knot only moves in steps the knot in front of it moves in, so
+        // longer ropes move their tail less often. They do not necessarily
+        // visit fewer positions though, see `more_knots_can_visit_more`.
+        #[test]
+        fn tail_moves_fall_with_knots(input in motions()) {
+            let moves: Vec<usize> = (1..10)
+                .map(|knots| {
+                    let path = tail_path(&input, knots);
+                    path.windows(2).filter(|pair| pair[0] != pair[1]).count()
+                })
+                .collect();
+            prop_assert!(moves.windows(2).all(|pair| pair[0] >= pair[1]), "{:?}", moves);
+        }
+    }
+
+    #[test]
+    fn more_knots_can_visit_more() {
+        let input = indoc! {"
+            R 4
+            U 7
+            R 9
+            R 6
+            U 5
+            D 6
+            D 4
+            D 2
+            L 5
+            U 5
+            L 9
+            U 1
+            D 3
+            L 3
+            R 8
+            D 4
+            R 7
+            L 5
+            R 2
+            L 5
+            U 5
+            R 8
+            L 9
+            L 8
+            U 4
+            D 6
+            L 6
+        "};
+        assert_eq!(calculate_tail_pos(input, 8), 27);
+        assert_eq!(calculate_tail_pos(input, 9), 28);
+    }
 
     #[test]
     fn sample_day_1() {
@@ -123,7 +140,7 @@ mod tests {
             L 5
             R 2
         "};
-        let result = solve_day1(&input);
+        let result = solve_day1(input);
         assert_eq!(result, 13);
     }
 
@@ -139,7 +156,7 @@ mod tests {
             L 5
             R 2
         "};
-        let result = solve_day2(&input);
+        let result = solve_day2(input);
         assert_eq!(result, 1);
     }
 
@@ -155,7 +172,7 @@ mod tests {
             L 25
             U 20
         "};
-        let result = solve_day2(&input);
+        let result = solve_day2(input);
         assert_eq!(result, 36);
     }
 }
@@ -1,3 +1,4 @@
+use crate::etc::ocr;
 use crate::{Solution, SolutionPair};
 
 ///////////////////////////////////////////////////////////////////////////////
@@ -7,7 +8,7 @@ enum MODE {
     ADDX(i32, i32),
 }
 
-fn solve_day1(input: &str) -> u64 {
+pub fn solve_day1(input: &str) -> u64 {
     let mut instructions = input.lines();
     let mut current_mode = MODE::IDLE;
     let mut x = 1;
@@ -33,7 +34,7 @@ fn solve_day1(input: &str) -> u64 {
                         panic!("Unknown instruction, {unknown_instruction}")
                     }
                     None => {
-                        println!("Instructions ended");
+                        // println!("Instructions ended");
                         break;
                     }
                 }
@@ -51,23 +52,25 @@ fn solve_day1(input: &str) -> u64 {
     s.try_into().unwrap()
 }
 
-fn solve_day2(input: &str) -> String {
+/// Rows of the picture drawn on the CRT.
+pub fn crt(input: &str) -> Vec<String> {
     let mut instructions = input.lines();
     let mut current_mode = MODE::IDLE;
     let mut x = 1;
-    let mut solution = String::with_capacity(6 * 40);
+    let mut solution = vec![String::with_capacity(40)];
     for cycle in 1..=240 {
         // println!("Cycle {}, x {},  MODE {:?}", cycle, x, current_mode);
         let crt_pos = (cycle - 1) % 40;
         // sprite goes from x-1 to x + 1
         // if crt_pos is on sprite -> draw #
+        let row = solution.last_mut().unwrap();
         if (x - 1) <= crt_pos && (x + 1) >= crt_pos {
-            solution.push('#');
+            row.push('#');
         } else {
-            solution.push('.');
+            row.push('.');
         }
-        if crt_pos == 39 {
-            solution.push('\n');
+        if crt_pos == 39 && cycle < 240 {
+            solution.push(String::with_capacity(40));
         }
 
         match current_mode {
@@ -84,7 +87,7 @@ fn solve_day2(input: &str) -> String {
                         panic!("Unknown instruction, {unknown_instruction}")
                     }
                     None => {
-                        println!("Instructions ended");
+                        // println!("Instructions ended");
                         break;
                     }
                 }
@@ -100,20 +103,27 @@ fn solve_day2(input: &str) -> String {
     solution
 }
 
-pub fn solve() -> SolutionPair {
-    let input = include_str!("../../input/day10.txt");
-    let sol1 = solve_day1(&input);
-    let sol2 = solve_day2(&input);
+/// Letters drawn on the CRT.
+pub fn solve_day2(input: &str) -> Result<String, ocr::Error> {
+    ocr::decode(&crt(input))
+}
+
+/// The CRT's picture behind the answer of part 2.
+pub fn explain(input: &str) -> Solution {
+    Solution::from(crt(input))
+}
+
+pub fn solve(input: &str) -> SolutionPair {
+    let sol1 = solve_day1(input);
+    let sol2 = solve_day2(input);
 
-    (
-        Solution::from(sol1),
-        Solution::from("\n".to_owned() + &sol2),
-    )
+    (Solution::from(sol1), Solution::from(sol2))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::etc::snapshot::assert_snapshot;
     use indoc::indoc;
 
     #[test]
@@ -123,7 +133,7 @@ mod tests {
             addx 3
             addx -5
         "};
-        let result = solve_day1(&input);
+        let result = solve_day1(input);
         assert_eq!(result, 0);
     }
 
@@ -277,12 +287,18 @@ mod tests {
             noop
             noop
         "};
-        let result = solve_day1(&input);
+        let result = solve_day1(input);
         assert_eq!(result, 13140);
     }
 
     #[test]
-    fn sample_day_2() {
+    fn unreadable_crt() {
+        let result = solve_day2("noop\naddx 3\naddx -5\n");
+        assert_eq!(result, Err(ocr::Error::Height(1)));
+    }
+
+    #[test]
+    fn sample_crt() {
         let input = indoc! {"
         addx 15
         addx -11
@@ -431,17 +447,7 @@ mod tests {
         noop
         noop
         "};
-        let result = solve_day2(&input);
-        assert_eq!(
-            result,
-            indoc! {"
-            ##..##..##..##..##..##..##..##..##..##..
-            ###...###...###...###...###...###...###.
-            ####....####....####....####....####....
-            #####.....#####.....#####.....#####.....
-            ######......######......######......####
-            #######.......#######.......#######.....
-        "}
-        );
+        let result = crt(input);
+        assert_snapshot("day10_example_crt", &(result.join("\n") + "\n"));
     }
 }
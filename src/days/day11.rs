@@ -1,3 +1,4 @@
+use crate::etc::trace;
 use crate::{Solution, SolutionPair};
 use gcd::Gcd;
 use std::fmt::{self, Debug};
@@ -5,7 +6,7 @@ use std::fmt::{self, Debug};
 type Worry = u128;
 type WorryOp = Box<dyn Fn(Worry) -> Worry>;
 
-struct Monkey {
+pub struct Monkey {
     items: Vec<Worry>,
     operation: WorryOp,
     divisor: Worry,
@@ -67,7 +68,8 @@ fn parse_operation(op_input: &str) -> WorryOp {
     }
 }
 
-fn parse_input(input: &str) -> Vec<Monkey> {
+pub fn parse_input(input: &str) -> Vec<Monkey> {
+    let _span = trace::span("parse");
     let lines: Vec<_> = input.lines().collect();
     let monkies: Vec<_> = lines
         .chunks(7)
@@ -93,9 +95,10 @@ fn parse_input(input: &str) -> Vec<Monkey> {
     monkies
 }
 
-fn solve_day1(input: &str) -> u64 {
+pub fn solve_day1(input: &str) -> u64 {
     let mut monkies = parse_input(input);
     for _round in 0..20 {
+        let _span = trace::span("round");
         for i in 0..monkies.len() {
             while monkies[i].items.len() > 0 {
                 monkies[i].inspect_count += 1;
@@ -127,13 +130,14 @@ fn solve_day1(input: &str) -> u64 {
     top_2_inspect_counts_product
 }
 
-fn solve_day2(input: &str) -> u64 {
+pub fn solve_day2(input: &str) -> u64 {
     let mut monkies = parse_input(input);
     let divisors = monkies.iter().map(|monkey| monkey.divisor);
 
     let lcm: u128 =
         divisors.clone().product::<u128>() / divisors.fold(0u128, |acc, divisor| acc.gcd(divisor));
     for _round in 0..10_000 {
+        let _span = trace::span("round");
         for i in 0..monkies.len() {
             while monkies[i].items.len() > 0 {
                 monkies[i].inspect_count += 1;
@@ -165,10 +169,9 @@ fn solve_day2(input: &str) -> u64 {
     top_2_inspect_counts_product
 }
 
-pub fn solve() -> SolutionPair {
-    let input = include_str!("../../input/day11.txt");
-    let sol1 = solve_day1(&input);
-    let sol2 = solve_day2(&input);
+pub fn solve(input: &str) -> SolutionPair {
+    let sol1 = solve_day1(input);
+    let sol2 = solve_day2(input);
 
     (Solution::from(sol1), Solution::from(sol2))
 }
@@ -177,6 +180,34 @@ pub fn solve() -> SolutionPair {
 mod tests {
     use super::*;
     use indoc::indoc;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn parse_notes(
+            items in prop::collection::vec(0..100u128, 1..6),
+            (operator, operand) in (prop::sample::select(vec!['+', '*']), prop::option::of(1..20u128)),
+            divisor in 2..30u128,
+            (if_true, if_false) in (0..8usize, 0..8usize),
+            x in 0..1000u128,
+        ) {
+            let items_list: Vec<String> = items.iter().map(u128::to_string).collect();
+            let operand_text = operand.map_or("old".to_owned(), |n| n.to_string());
+            let input = format!(
+                "Monkey 0:\n  Starting items: {}\n  Operation: new = old {operator} {operand_text}\n  Test: divisible by {divisor}\n    If true: throw to monkey {if_true}\n    If false: throw to monkey {if_false}\n",
+                items_list.join(", ")
+            );
+            let monkeys = parse_input(&input);
+            prop_assert_eq!(monkeys.len(), 1);
+            let monkey = &monkeys[0];
+            let operand = operand.unwrap_or(x);
+            let expected = if operator == '+' { x + operand } else { x * operand };
+            prop_assert_eq!(&monkey.items, &items);
+            prop_assert_eq!((monkey.operation)(x), expected);
+            prop_assert_eq!(monkey.divisor, divisor);
+            prop_assert_eq!((monkey.true_monkey, monkey.false_monkey), (if_true, if_false));
+        }
+    }
 
     #[test]
     fn sample_day_1() {
@@ -209,7 +240,7 @@ mod tests {
           If true: throw to monkey 0
           If false: throw to monkey 1
         "};
-        let result = solve_day1(&input);
+        let result = solve_day1(input);
         assert_eq!(result, 10605);
     }
 
@@ -244,7 +275,7 @@ mod tests {
           If true: throw to monkey 0
           If false: throw to monkey 1
         "};
-        let result = solve_day2(&input);
+        let result = solve_day2(input);
         assert_eq!(result, 2713310158);
     }
 }
@@ -1,124 +1,82 @@
-use std::collections::{HashSet, VecDeque};
-
+use crate::etc::grid::{Grid, Point};
+use crate::etc::search::{self, Search};
+use crate::etc::trace;
 use crate::{Solution, SolutionPair};
 
-type Coordinates = (i32, i32);
+type Coordinates = Point;
 
 const LOWEST_ELEVATION: u32 = 'a' as u32;
 const HIGHEST_ELEVATION: u32 = 'z' as u32;
 
-struct Map {
-    elevations: Vec<Vec<u32>>,
+pub struct Map {
+    elevations: Grid<u32>,
     current_position: Coordinates,
     best_signal_location: Coordinates,
-    width: usize,
-    height: usize,
 }
 
-impl Map {
-    fn neighbor_elevations(&self, current_position: Coordinates) -> Vec<(Coordinates, u32)> {
-        [(-1, 0), (1, 0), (0, -1), (0, 1)]
-            .into_iter()
-            .map(|(x, y)| (current_position.0 + x, current_position.1 + y))
-            .filter(|(x, y)| {
-                *x >= 0 && (*x as usize) < self.width && *y >= 0 && (*y as usize) < self.height
-            })
-            .map(|(x, y)| ((x, y), self.elevations[y as usize][x as usize]))
-            .collect()
+pub fn parse(input: &str) -> Map {
+    let _span = trace::span("parse");
+    let marked = Grid::parse(input, |c| c);
+    let position = |mark| {
+        marked
+            .position(|&c| c == mark)
+            .unwrap_or_else(|| panic!("no {mark} in the map"))
+    };
+
+    Map {
+        elevations: marked.map(|_, &elevation| match elevation {
+            'S' => LOWEST_ELEVATION,
+            'E' => HIGHEST_ELEVATION,
+            elevation => elevation as u32,
+        }),
+        current_position: position('S'),
+        best_signal_location: position('E'),
     }
 }
 
-fn parse(input: &str) -> Map {
-    let mut current_position = (0, 0);
-    let mut best_signal_location = (0, 0);
-    let mut elevations = Vec::new();
-
-    for (y, row) in input.lines().enumerate() {
-        let mut row_elevations = Vec::with_capacity(row.len());
-
-        for (x, elevation) in row.chars().enumerate() {
-            match elevation {
-                'S' => {
-                    current_position = (x as i32, y as i32);
-                    row_elevations.push(LOWEST_ELEVATION)
-                }
-                'E' => {
-                    best_signal_location = (x as i32, y as i32);
-                    row_elevations.push(HIGHEST_ELEVATION)
-                }
-                elevation => row_elevations.push(elevation as u32),
-            };
-        }
-
-        elevations.push(row_elevations);
+impl Map {
+    /// Neighbours of `position` that are at most one higher, so that they can
+    /// be climbed to.
+    fn climbable(&self, position: Coordinates) -> impl Iterator<Item = Coordinates> + '_ {
+        let elevation = self.elevations[position];
+        self.elevations
+            .neighbours4(position)
+            .filter(move |&(_, &neighbor_elevation)| neighbor_elevation <= elevation + 1)
+            .map(|(neighbor_position, _)| neighbor_position)
     }
 
-    let width = elevations[0].len();
-    let height = elevations.len();
-
-    Map {
-        elevations,
-        current_position,
-        best_signal_location,
-        width,
-        height,
+    /// Shortest climb from any of `starts` to the best signal.
+    fn climb(&self, starts: impl IntoIterator<Item = Coordinates>) -> Search<Coordinates, usize> {
+        search::bfs(
+            starts,
+            |&position| self.climbable(position),
+            |&position| position == self.best_signal_location,
+        )
     }
 }
 
-fn solve_day1(input: &str) -> u64 {
+pub fn solve_day1(input: &str) -> Result<u64, &'static str> {
     let map = parse(input);
-    let current_position = map.current_position;
-    let mut queue = VecDeque::from(vec![(current_position, LOWEST_ELEVATION, 0)]);
-    let mut visited: HashSet<Coordinates> = HashSet::from([current_position]);
-
-    while !queue.is_empty() {
-        let (current_position, current_elevation, path_length) = queue.pop_front().unwrap();
-
-        if current_position == map.best_signal_location {
-            return path_length;
-        }
-
-        for (neighbor_position, neighbor_elevation) in map.neighbor_elevations(current_position) {
-            if neighbor_elevation as i32 - current_elevation as i32 <= 1
-                && !visited.contains(&neighbor_position)
-            {
-                queue.push_back((neighbor_position, neighbor_elevation, path_length + 1));
-                visited.insert(neighbor_position);
-            }
-        }
-    }
-
-    0
+    let search = map.climb([map.current_position]);
+    let steps = search.cost().ok_or("no path to the best signal")?;
+    Ok(steps as u64)
 }
-fn solve_day2(input: &str) -> u64 {
-    let map = parse(input);
-    let current_position = map.best_signal_location;
-    let mut queue = VecDeque::from(vec![(current_position, HIGHEST_ELEVATION, 0)]);
-    let mut visited: HashSet<Coordinates> = HashSet::from([current_position]);
-
-    while !queue.is_empty() {
-        let (current_position, current_elevation, path_length) = queue.pop_front().unwrap();
-
-        if current_elevation == LOWEST_ELEVATION {
-            return path_length;
-        }
-
-        for (neighbor_position, neighbor_elevation) in map.neighbor_elevations(current_position) {
-            if current_elevation as i32 - neighbor_elevation as i32 <= 1
-                && !visited.contains(&neighbor_position)
-            {
-                queue.push_back((neighbor_position, neighbor_elevation, path_length + 1));
-                visited.insert(neighbor_position);
-            }
-        }
-    }
 
-    0
+pub fn solve_day2(input: &str) -> Result<u64, &'static str> {
+    let map = parse(input);
+    let lowest = map
+        .elevations
+        .iter()
+        .filter(|&(_, &elevation)| elevation == LOWEST_ELEVATION)
+        .map(|(position, _)| position);
+    let search = map.climb(lowest);
+    let steps = search.cost().ok_or("no path from the lowest elevation")?;
+    Ok(steps as u64)
 }
-pub fn solve() -> SolutionPair {
-    let input = include_str!("../../input/day12.txt");
-    let sol1: u64 = solve_day1(&input);
-    let sol2: u64 = solve_day2(&input);
+
+pub fn solve(input: &str) -> SolutionPair {
+    let sol1 = solve_day1(input);
+    let sol2 = solve_day2(input);
 
     (Solution::from(sol1), Solution::from(sol2))
 }
@@ -137,8 +95,8 @@ mod tests {
             acctuvwj
             abdefghi
         "};
-        let result = solve_day1(&input);
-        assert_eq!(result, 31);
+        let result = solve_day1(input);
+        assert_eq!(result, Ok(31));
     }
 
     #[test]
@@ -150,7 +108,42 @@ mod tests {
             acctuvwj
             abdefghi
         "};
-        let result = solve_day2(&input);
-        assert_eq!(result, 29);
+        let result = solve_day2(input);
+        assert_eq!(result, Ok(29));
+    }
+
+    #[test]
+    fn climb_along_the_path() {
+        let input = indoc! {"
+            Sabqponm
+            abcryxxl
+            accszExk
+            acctuvwj
+            abdefghi
+        "};
+        let map = parse(input);
+        let path = map.climb([map.current_position]).path().unwrap();
+        assert_eq!(path.len(), 32);
+        assert_eq!(path[0], map.current_position);
+        assert_eq!(path[31], map.best_signal_location);
+        assert!(path
+            .windows(2)
+            .all(|step| map.climbable(step[0]).any(|next| next == step[1])));
+    }
+
+    #[test]
+    fn no_path() {
+        let input = indoc! {"
+            SbcE
+            azzz
+        "};
+        assert_eq!(solve_day1(input), Err("no path to the best signal"));
+        assert_eq!(solve_day2(input), Err("no path from the lowest elevation"));
+    }
+
+    #[test]
+    #[should_panic(expected = "no E in the map")]
+    fn no_best_signal() {
+        parse("Sabc\n");
     }
 }
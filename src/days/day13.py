@@ -1,8 +1,10 @@
 
 from functools import cmp_to_key
 from math import prod
+import sys
 
-input =  open("input/day13.txt").read()
+# `day13.py -` reads the input from stdin, as the differential tests do.
+input = sys.stdin.read() if sys.argv[1:] == ["-"] else open("input/day13.txt").read()
 
 
 def cmp(l, r):
@@ -1,4 +1,5 @@
 use std::cmp::Ordering;
+use std::fmt::{self, Display, Formatter};
 
 use crate::{Solution, SolutionPair};
 
@@ -9,7 +10,7 @@ pub enum Packet {
 }
 
 impl Packet {
-    fn parse_one(s: &str) -> (Self, &str) {
+    pub fn parse_one(s: &str) -> (Self, &str) {
         if let Some(mut s) = s.strip_prefix('[') {
             let mut list = vec![];
             if let Some(trailing) = s.strip_prefix(']') {
@@ -46,6 +47,24 @@ impl Packet {
     }
 }
 
+impl Display for Packet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Int(value) => value.fmt(f),
+            Self::List(list) => {
+                f.write_str("[")?;
+                for (i, packet) in list.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(",")?;
+                    }
+                    packet.fmt(f)?;
+                }
+                f.write_str("]")
+            }
+        }
+    }
+}
+
 impl Ord for Packet {
     fn cmp(&self, other: &Self) -> Ordering {
         if let (Self::Int(a), Self::Int(b)) = (self, other) {
@@ -68,7 +87,7 @@ impl PartialEq for Packet {
     }
 }
 
-fn solve_day1(input: &str) -> u64 {
+pub fn solve_day1(input: &str) -> u64 {
     let packages: Vec<Packet> = input
         .lines()
         .filter(|line| !line.is_empty())
@@ -91,7 +110,7 @@ fn solve_day1(input: &str) -> u64 {
     sorted
 }
 
-fn solve_day2(input: &str) -> u64 {
+pub fn solve_day2(input: &str) -> u64 {
     let mut packages: Vec<Packet> = input
         .lines()
         .filter(|line| !line.is_empty())
@@ -114,10 +133,9 @@ fn solve_day2(input: &str) -> u64 {
 
     decoder_key as u64
 }
-pub fn solve() -> SolutionPair {
-    let input = include_str!("../../input/day13.txt");
-    let sol1: u64 = solve_day1(&input);
-    let sol2: u64 = solve_day2(&input);
+pub fn solve(input: &str) -> SolutionPair {
+    let sol1: u64 = solve_day1(input);
+    let sol2: u64 = solve_day2(input);
 
     (Solution::from(sol1), Solution::from(sol2))
 }
@@ -126,6 +144,34 @@ pub fn solve() -> SolutionPair {
 mod tests {
     use super::*;
     use indoc::indoc;
+    use proptest::prelude::*;
+
+    fn packet() -> impl Strategy<Value = Packet> {
+        let int = (0..=10u32).prop_map(Packet::Int);
+        let packet = int.prop_recursive(4, 32, 5, |inner| {
+            prop::collection::vec(inner, 0..5).prop_map(Packet::List)
+        });
+        prop::collection::vec(packet, 0..5).prop_map(Packet::List)
+    }
+
+    proptest! {
+        #[test]
+        fn print_parse_round_trip(packet in packet()) {
+            let printed = packet.to_string();
+            let (parsed, trailing) = Packet::parse_one(&printed);
+            prop_assert_eq!(trailing, "");
+            prop_assert_eq!(parsed.to_string(), printed);
+        }
+
+        #[test]
+        fn ordering_is_total(a in packet(), b in packet(), c in packet()) {
+            prop_assert_eq!(a.cmp(&b), b.cmp(&a).reverse());
+            prop_assert_eq!(a == b, a.cmp(&b).is_eq());
+            if a <= b && b <= c {
+                prop_assert!(a <= c, "{} <= {} <= {}", a, b, c);
+            }
+        }
+    }
 
     #[test]
     fn sample_day_1() {
@@ -154,7 +200,7 @@ mod tests {
             [1,[2,[3,[4,[5,6,7]]]],8,9]
             [1,[2,[3,[4,[5,6,0]]]],8,9]
         "};
-        let result = solve_day1(&input);
+        let result = solve_day1(input);
         assert_eq!(result, 13);
     }
 
@@ -185,7 +231,7 @@ mod tests {
             [1,[2,[3,[4,[5,6,7]]]],8,9]
             [1,[2,[3,[4,[5,6,0]]]],8,9]
         "};
-        let result = solve_day2(&input);
+        let result = solve_day2(input);
         assert_eq!(result, 140);
     }
 }
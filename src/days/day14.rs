@@ -1,147 +1,127 @@
-use std::collections::HashMap;
-
+use crate::etc::geom::BoundingBox;
+use crate::etc::grid::{Grid, Point};
+use crate::etc::trace;
 use crate::{Solution, SolutionPair};
 
-type Coordinates = (i64, i64); // x, y
+type Coordinates = Point;
 
-fn solve_day1(input: &str) -> u64 {
-    let mut map = parse(input);
-    loop {
-        // spawn sand
-        println!("spawn");
-        let mut current = (500, 0);
-        let mut could_place = false;
-
-        // let sand flow for some ticks
-        for _tick in 0..1_000 {
-            // check if able to move
-            let possibilities = [(0, 1), (-1, 1), (1, 1)];
-            let mut found_possiblity = false;
-            for possibility in possibilities {
-                let next_pos = (current.0 + possibility.0, current.1 + possibility.1);
-                // println!("{next_pos:?}");
-                match map.get(&next_pos) {
-                    None => {
-                        // field is empty at target so move there
-                        current = next_pos;
-                        found_possiblity = true;
-                        break;
-                    }
-                    // TODO: check for start
-                    Some(_) => {
-                        // field is blocked by whatever, try next
-                        continue;
-                    }
-                }
-            }
+const SOURCE: Coordinates = Point::new(500, 0);
 
-            // if nothing possible. place sand forever.
-            if !found_possiblity {
-                map.insert(current, 'o');
-                could_place = true;
-                break;
-            }
-        }
-        if !could_place {
-            break;
-        }
-    }
-    println!("{map:?}");
-    let found_sand = map.iter().filter(|(_coords, &item)| item == 'o').count();
-    found_sand as u64
+/// Slice of the cave wide enough for sand from the source to pile up on the
+/// floor two below the lowest rock, with its top left corner at `corner`.
+pub struct Cave {
+    tiles: Grid<char>,
+    corner: Coordinates,
+    floor: i64,
 }
 
-fn solve_day2(input: &str) -> u64 {
-    let mut map = parse(input);
+impl Cave {
+    pub fn get(&self, point: Coordinates) -> Option<char> {
+        self.tiles.get(point - self.corner).copied()
+    }
 
-    // add groundfloor
-    let grondfloor_y = map.keys().map(|(_x, y)| y).max().unwrap() + 2;
-    for x in -1_000_000..1_000_000 {
-        map.insert((x, grondfloor_y), '#');
+    fn set(&mut self, point: Coordinates, tile: char) {
+        self.tiles[point - self.corner] = tile;
     }
 
-    loop {
-        // spawn sand
-        // println!("spawn");
-        let mut current = (500, 0);
-
-        // let sand flow for some ticks
-        loop {
-            // check if able to move
-            let possibilities = [(0, 1), (-1, 1), (1, 1)];
-            let mut found_possiblity = false;
-            for possibility in possibilities {
-                let next_pos = (current.0 + possibility.0, current.1 + possibility.1);
-                // println!("{next_pos:?}");
-                match map.get(&next_pos) {
-                    None => {
-                        // field is empty at target so move there
+    /// Points of all tiles that are `tile`.
+    pub fn find(&self, tile: char) -> impl Iterator<Item = Coordinates> + '_ {
+        self.tiles
+            .iter()
+            .filter(move |(_, &other)| other == tile)
+            .map(|(point, _)| point + self.corner)
+    }
+
+    /// Where a unit of sand from the source comes to rest, or `None` if it falls
+    /// out of the bottom.
+    fn drop_sand(&self) -> Option<Coordinates> {
+        let mut current = SOURCE;
+        'falling: loop {
+            for dx in [0, -1, 1] {
+                let next_pos = current + Point::new(dx, 1);
+                match self.get(next_pos) {
+                    // field is empty at target so move there
+                    Some('.') => {
                         current = next_pos;
-                        found_possiblity = true;
-                        break;
-                    }
-                    Some(_) => {
-                        // field is blocked by whatever, try next
-                        continue;
+                        continue 'falling;
                     }
+                    // field is blocked by whatever, try next
+                    Some(_) => {}
+                    None => return None,
                 }
             }
-
-            // if nothing possible. place sand forever.
-            if !found_possiblity && current == (500, 0) {
-                map.insert(current, 'o');
-
-                let found_sand = map.iter().filter(|(_coords, &item)| item == 'o').count();
-                return found_sand as u64;
-            } else if !found_possiblity {
-                map.insert(current, 'o');
-                break;
-            }
+            return Some(current);
         }
     }
 }
 
-fn parse(input: &str) -> HashMap<Coordinates, char> {
-    let mut map = HashMap::new();
+pub fn solve_day1(input: &str) -> u64 {
+    let mut map = parse(input);
+    // without a floor, sand that gets past the lowest rock falls out of the map
+    while let Some(rest) = map.drop_sand() {
+        map.set(rest, 'o');
+    }
+    map.find('o').count() as u64
+}
 
-    for line in input.lines() {
-        let coords = line.split_terminator(" -> ");
-        for (point, next_point) in coords.clone().zip(coords.skip(1)) {
-            //println!("{point} {next_point}");
-            let (x, y) = point.split_once(",").unwrap();
-            let (next_x, next_y) = next_point.split_once(",").unwrap();
+pub fn solve_day2(input: &str) -> u64 {
+    let mut map = parse(input);
 
-            let parse = |s: &str| s.parse::<i64>().unwrap();
-            let order = |i1: i64, i2: i64| {
-                if i1 > i2 {
-                    (i2, i1)
-                } else {
-                    (i1, i2)
-                }
-            };
+    // add groundfloor
+    for x in map.corner.x..map.corner.x + map.tiles.width() as i64 {
+        map.set(Point::new(x, map.floor), '#');
+    }
 
-            let (x, next_x) = order(parse(x), parse(next_x));
-            let (y, next_y) = order(parse(y), parse(next_y));
+    loop {
+        let rest = map.drop_sand().expect("the floor stops all sand");
+        map.set(rest, 'o');
+        if rest == SOURCE {
+            return map.find('o').count() as u64;
+        }
+    }
+}
 
-            for x in x..=next_x {
-                for y in y..=next_y {
-                    //println!("{x} {y}");
-                    map.insert((x, y), '#');
+pub fn parse(input: &str) -> Cave {
+    let _span = trace::span("parse");
+    let parse_point = |point: &str| {
+        let (x, y) = point.split_once(",").unwrap();
+        Point::new(x.parse::<i64>().unwrap(), y.parse::<i64>().unwrap())
+    };
+    let paths: Vec<Vec<Coordinates>> = input
+        .lines()
+        .map(|line| line.split_terminator(" -> ").map(parse_point).collect())
+        .collect();
+
+    let mut bounds = BoundingBox::of(paths.iter().flatten().copied().chain([SOURCE])).unwrap();
+    let floor = bounds.max.y + 2;
+    // sand piles up at most `floor` to either side of the source
+    bounds.include(SOURCE + Point::new(-floor - 1, floor));
+    bounds.include(SOURCE + Point::new(floor + 1, floor));
+    let size = bounds.size();
+    let mut map = Cave {
+        tiles: Grid::new(size.x as usize, size.y as usize, '.'),
+        corner: bounds.min,
+        floor,
+    };
+
+    for path in &paths {
+        for pair in path.windows(2) {
+            let (start, end) = (pair[0], pair[1]);
+            for x in start.x.min(end.x)..=start.x.max(end.x) {
+                for y in start.y.min(end.y)..=start.y.max(end.y) {
+                    map.set(Point::new(x, y), '#');
                 }
             }
         }
     }
 
-    //println!("{map:?}");
-    //println!("{}", map.len());
-    map.insert((500, 0), '+');
+    map.set(SOURCE, '+');
     map
 }
 
-pub fn solve() -> SolutionPair {
-    let input = include_str!("../../input/day14.txt");
-    let sol1: u64 = solve_day1(&input);
-    let sol2: u64 = solve_day2(&input);
+pub fn solve(input: &str) -> SolutionPair {
+    let sol1: u64 = solve_day1(input);
+    let sol2: u64 = solve_day2(input);
 
     (Solution::from(sol1), Solution::from(sol2))
 }
@@ -150,6 +130,53 @@ pub fn solve() -> SolutionPair {
 mod tests {
     use super::*;
     use indoc::indoc;
+    use proptest::prelude::*;
+    use std::collections::HashSet;
+
+    fn path() -> impl Strategy<Value = Vec<Coordinates>> {
+        let step = (any::<bool>(), -5..=5i64);
+        ((480..520i64, 1..30i64), prop::collection::vec(step, 1..5)).prop_map(|(start, steps)| {
+            let mut points = vec![Point::from(start)];
+            for (horizontal, distance) in steps {
+                let last = *points.last().unwrap();
+                points.push(if horizontal {
+                    last + Point::new(distance, 0)
+                } else {
+                    Point::new(last.x, (last.y + distance).max(1))
+                });
+            }
+            points
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn parse_rock_paths(paths in prop::collection::vec(path(), 1..5)) {
+            let mut rocks = HashSet::new();
+            for path in &paths {
+                for pair in path.windows(2) {
+                    let (start, end) = (pair[0], pair[1]);
+                    for x in start.x.min(end.x)..=start.x.max(end.x) {
+                        for y in start.y.min(end.y)..=start.y.max(end.y) {
+                            rocks.insert(Point::new(x, y));
+                        }
+                    }
+                }
+            }
+            let lines: Vec<String> = paths
+                .iter()
+                .map(|path| {
+                    let points: Vec<String> = path.iter().map(|point| format!("{},{}", point.x, point.y)).collect();
+                    points.join(" -> ")
+                })
+                .collect();
+
+            let map = parse(&lines.join("\n"));
+            prop_assert_eq!(map.get(SOURCE), Some('+'));
+            let parsed: HashSet<Coordinates> = map.find('#').collect();
+            prop_assert_eq!(parsed, rocks);
+        }
+    }
 
     #[test]
     fn sample_day_1() {
@@ -157,7 +184,7 @@ mod tests {
             498,4 -> 498,6 -> 496,6
             503,4 -> 502,4 -> 502,9 -> 494,9
         "};
-        let result = solve_day1(&input);
+        let result = solve_day1(input);
         assert_eq!(result, 24);
     }
 
@@ -167,7 +194,7 @@ mod tests {
             498,4 -> 498,6 -> 496,6
             503,4 -> 502,4 -> 502,9 -> 494,9
         "};
-        let result = solve_day2(&input);
+        let result = solve_day2(input);
         assert_eq!(result, 93);
     }
 }
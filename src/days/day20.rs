@@ -2,10 +2,10 @@ use crate::{Solution, SolutionPair};
 
 ///////////////////////////////////////////////////////////////////////////////
 
-pub fn solve() -> SolutionPair {
+pub fn solve(_input: &str) -> SolutionPair {
     // Your solution here...
-    let sol1: u64 = 0;
-    let sol2: u64 = 0;
+    let sol1 = Solution::Unsolved;
+    let sol2 = Solution::Unsolved;
 
-    (Solution::from(sol1), Solution::from(sol2))
+    (sol1, sol2)
 }
@@ -0,0 +1,366 @@
+use std::fmt::{Display, Formatter, Result};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::Solution;
+
+/// Why AoC rejected a submitted answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+    Unknown,
+}
+
+/// Outcome of comparing a computed `Solution` against the answers log.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Check {
+    /// Nothing is known about this part yet.
+    Unknown,
+    /// Matches the answer AoC accepted.
+    Correct,
+    /// Differs from the answer AoC accepted.
+    Mismatch(String),
+    /// Was already submitted and rejected.
+    Rejected(Hint),
+    /// Is at least as high as an answer rejected for being too high.
+    AboveBound(String),
+    /// Is at most as low as an answer rejected for being too low.
+    BelowBound(String),
+    /// Was not rejected and lies within the known bounds.
+    Plausible,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct PartLog {
+    pub correct: Option<String>,
+    pub rejected: Vec<(String, Hint)>,
+}
+
+/// Answers log of a single day, stored in `answers/dayNN.txt`.
+///
+/// Every line holds the part, a marker and the answer:
+/// `=` accepted, `>` too high, `<` too low and `!` rejected without a hint.
+/// Empty lines and lines starting with `#` are ignored. Saving appends the
+/// answers recorded since loading, so such notes are kept.
+///
+/// ```text
+/// 1 = 24000
+/// 2 > 50000
+/// 2 < 41000
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct AnswerLog {
+    parts: [PartLog; 2],
+    /// Lines of the answers recorded since loading, still to be saved.
+    unsaved: Vec<String>,
+}
+
+/// Line of an answers log, or of the progress file, that could not be read.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// File the line was read from, if any.
+    pub path: Option<PathBuf>,
+    pub line: usize,
+    pub message: String,
+}
+
+impl AnswerLog {
+    pub fn path(day: u8) -> PathBuf {
+        PathBuf::from(format!("answers/day{day:02}.txt"))
+    }
+
+    /// Answers log of `day`, which is empty if there is no file for it yet.
+    pub fn load(day: u8) -> std::result::Result<Self, ParseError> {
+        Self::load_from(&Self::path(day))
+    }
+
+    pub fn load_from(path: &Path) -> std::result::Result<Self, ParseError> {
+        match fs::read_to_string(path) {
+            Ok(content) => Self::parse(&content).map_err(|e| ParseError {
+                path: Some(path.to_owned()),
+                ..e
+            }),
+            Err(_) => Ok(Self::default()),
+        }
+    }
+
+    /// Appends the answers recorded since loading to the log of `day`.
+    pub fn save(&mut self, day: u8) -> io::Result<()> {
+        self.save_to(&Self::path(day))
+    }
+
+    pub fn save_to(&mut self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut content = fs::read_to_string(path).unwrap_or_default();
+        if !content.is_empty() && !content.ends_with('\n') {
+            content.push('\n');
+        }
+        for line in self.unsaved.drain(..) {
+            content.push_str(&line);
+            content.push('\n');
+        }
+        fs::write(path, content)
+    }
+
+    pub fn parse(content: &str) -> std::result::Result<Self, ParseError> {
+        let mut log = Self::default();
+        for (number, line) in (1..).zip(content.lines().map(str::trim)) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let error = |message: String| ParseError {
+                path: None,
+                line: number,
+                message,
+            };
+            let mut fields = line.splitn(3, ' ');
+            let (part, marker, answer) = match (fields.next(), fields.next(), fields.next()) {
+                (Some(part), Some(marker), Some(answer)) => (part, marker, answer.trim()),
+                _ => {
+                    return Err(error(format!(
+                        "expected `<part> <marker> <answer>`, got `{line}`"
+                    )))
+                }
+            };
+            let part = match part {
+                "1" | "2" => log.part_mut(part.parse().unwrap()),
+                _ => return Err(error(format!("part must be 1 or 2, got `{part}`"))),
+            };
+            match marker {
+                "=" => part.correct = Some(answer.to_owned()),
+                ">" => part.rejected.push((answer.to_owned(), Hint::TooHigh)),
+                "<" => part.rejected.push((answer.to_owned(), Hint::TooLow)),
+                "!" => part.rejected.push((answer.to_owned(), Hint::Unknown)),
+                _ => return Err(error(format!("unknown marker `{marker}`"))),
+            }
+        }
+        Ok(log)
+    }
+
+    pub fn part(&self, part: u8) -> &PartLog {
+        match part {
+            1 | 2 => &self.parts[part as usize - 1],
+            _ => panic!("Not a valid part: {part}"),
+        }
+    }
+
+    fn part_mut(&mut self, part: u8) -> &mut PartLog {
+        match part {
+            1 | 2 => &mut self.parts[part as usize - 1],
+            _ => panic!("Not a valid part: {part}"),
+        }
+    }
+
+    pub fn accept(&mut self, part: u8, answer: &str) {
+        let answer = answer.trim().to_owned();
+        self.unsaved.push(entry(part, '=', &answer));
+        self.part_mut(part).correct = Some(answer);
+    }
+
+    pub fn reject(&mut self, part: u8, answer: &str, hint: Hint) {
+        let log = self.part_mut(part);
+        if !log.rejected.iter().any(|(r, _)| same_answer(r, answer)) {
+            log.rejected.push((answer.trim().to_owned(), hint));
+            self.unsaved.push(entry(part, hint.marker(), answer.trim()));
+        }
+    }
+
+    pub fn check(&self, part: u8, solution: &Solution) -> Check {
+        if !solution.is_solved() {
+            return Check::Unknown;
+        }
+        let log = self.part(part);
+        let answer = solution.to_string();
+
+        if let Some(correct) = &log.correct {
+            return if same_answer(correct, &answer) {
+                Check::Correct
+            } else {
+                Check::Mismatch(correct.clone())
+            };
+        }
+
+        if log.rejected.is_empty() {
+            return Check::Unknown;
+        }
+        if let Some((_, hint)) = log.rejected.iter().find(|(r, _)| same_answer(r, &answer)) {
+            return Check::Rejected(*hint);
+        }
+
+        let value = match answer.trim().parse::<i128>() {
+            Ok(value) => value,
+            Err(_) => return Check::Plausible,
+        };
+        let bound = |wanted: Hint| {
+            log.rejected
+                .iter()
+                .filter(move |(_, hint)| *hint == wanted)
+                .filter_map(|(r, _)| r.parse::<i128>().ok().map(|v| (v, r)))
+        };
+        if let Some((_, high)) = bound(Hint::TooHigh).filter(|(v, _)| value >= *v).min() {
+            return Check::AboveBound(high.clone());
+        }
+        if let Some((_, low)) = bound(Hint::TooLow).filter(|(v, _)| value <= *v).max() {
+            return Check::BelowBound(low.clone());
+        }
+
+        Check::Plausible
+    }
+}
+
+fn entry(part: u8, marker: char, answer: &str) -> String {
+    format!("{part} {marker} {answer}")
+}
+
+fn same_answer(stored: &str, answer: &str) -> bool {
+    stored.trim().parse::<Solution>() == answer.trim().parse::<Solution>()
+}
+
+impl Display for AnswerLog {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        for (part, log) in (1..).zip(&self.parts) {
+            if let Some(correct) = &log.correct {
+                writeln!(f, "{}", entry(part, '=', correct))?;
+            }
+            for (answer, hint) in &log.rejected {
+                writeln!(f, "{}", entry(part, hint.marker(), answer))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Hint {
+    fn marker(self) -> char {
+        match self {
+            Hint::TooHigh => '>',
+            Hint::TooLow => '<',
+            Hint::Unknown => '!',
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        if let Some(path) = &self.path {
+            write!(f, "{}, ", path.display())?;
+        }
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl Display for Hint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Hint::TooHigh => "too high".fmt(f),
+            Hint::TooLow => "too low".fmt(f),
+            Hint::Unknown => "wrong".fmt(f),
+        }
+    }
+}
+
+impl Display for Check {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Check::Unknown | Check::Plausible => Ok(()),
+            Check::Correct => write!(f, "(verified)"),
+            Check::Mismatch(correct) => write!(f, "(!) accepted answer was {correct}"),
+            Check::Rejected(hint) => write!(f, "(!) already rejected as {hint}"),
+            Check::AboveBound(high) => write!(f, "(!) not below {high}, which was too high"),
+            Check::BelowBound(low) => write!(f, "(!) not above {low}, which was too low"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    fn log() -> AnswerLog {
+        AnswerLog::parse(indoc! {"
+            # day 1
+            1 = 24000
+            2 > 50000
+            2 < 41000
+            2 ! 45123
+        "})
+        .unwrap()
+    }
+
+    #[test]
+    fn check_known_answer() {
+        let log = log();
+        assert_eq!(log.check(1, &Solution::from(24000u64)), Check::Correct);
+        assert_eq!(
+            log.check(1, &Solution::from(1u64)),
+            Check::Mismatch("24000".to_owned())
+        );
+    }
+
+    #[test]
+    fn check_rejected_and_bounds() {
+        let log = log();
+        assert_eq!(
+            log.check(2, &Solution::from(45123u64)),
+            Check::Rejected(Hint::Unknown)
+        );
+        assert_eq!(
+            log.check(2, &Solution::from(50000i32)),
+            Check::Rejected(Hint::TooHigh)
+        );
+        assert_eq!(
+            log.check(2, &Solution::from(60000u64)),
+            Check::AboveBound("50000".to_owned())
+        );
+        assert_eq!(
+            log.check(2, &Solution::from(12u8)),
+            Check::BelowBound("41000".to_owned())
+        );
+        assert_eq!(log.check(2, &Solution::from(45000u64)), Check::Plausible);
+    }
+
+    #[test]
+    fn record_round_trips() {
+        let mut log = AnswerLog::default();
+        log.reject(1, "7", Hint::TooLow);
+        log.reject(1, "7", Hint::TooLow);
+        log.accept(1, "9");
+        log.reject(2, "abc", Hint::Unknown);
+        assert_eq!(log.to_string(), "1 = 9\n1 < 7\n2 ! abc\n");
+        assert_eq!(
+            AnswerLog::parse(&log.to_string()).unwrap().to_string(),
+            log.to_string()
+        );
+    }
+
+    #[test]
+    fn report_malformed_lines() {
+        let error = AnswerLog::parse("# notes\n1 = 5\n\n3 = 7\n").unwrap_err();
+        assert_eq!(error.line, 4);
+        assert_eq!(error.to_string(), "line 4: part must be 1 or 2, got `3`");
+        let error = AnswerLog::parse("1 ? 5").unwrap_err();
+        assert_eq!(error.to_string(), "line 1: unknown marker `?`");
+        assert!(AnswerLog::parse("1 =").is_err());
+    }
+
+    #[test]
+    fn save_keeps_notes() {
+        let path = std::env::temp_dir().join(format!("aoc-answers-{}.txt", std::process::id()));
+        fs::write(&path, "# tried by hand\n1 > 100").unwrap();
+        let mut log = AnswerLog::load_from(&path).unwrap();
+        log.reject(1, "100", Hint::TooHigh);
+        log.accept(1, "42");
+        log.save_to(&path).unwrap();
+        let content = fs::read_to_string(&path).unwrap();
+        assert_eq!(content, "# tried by hand\n1 > 100\n1 = 42\n");
+        // saving again does not repeat the answers
+        log.save_to(&path).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), content);
+        fs::remove_file(&path).unwrap();
+    }
+}
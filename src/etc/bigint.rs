@@ -0,0 +1,575 @@
+//! Integers of any size, for answers and intermediate values that overflow even
+//! `u128`.
+//!
+//! `BigInt` is a sign and a magnitude of base 2^32 limbs, with schoolbook
+//! multiplication and long division: plenty for puzzle sizes. `Int` is an
+//! `i128` that turns into a `BigInt` only once an operation would overflow it.
+
+use std::cmp::Ordering;
+use std::fmt::{self, Display, Formatter};
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+use std::str::FromStr;
+
+/// Integer of any size.
+///
+/// The magnitude has no leading zero limbs and zero is never negative, so the
+/// derived equality and hashing compare values.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BigInt {
+    negative: bool,
+    /// Little endian limbs.
+    magnitude: Vec<u32>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError;
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "not an integer")
+    }
+}
+
+impl BigInt {
+    fn new(negative: bool, mut magnitude: Vec<u32>) -> Self {
+        while magnitude.last() == Some(&0) {
+            magnitude.pop();
+        }
+        Self {
+            negative: negative && !magnitude.is_empty(),
+            magnitude,
+        }
+    }
+
+    pub fn zero() -> Self {
+        Self::default()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.magnitude.is_empty()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    pub fn abs(&self) -> Self {
+        Self::new(false, self.magnitude.clone())
+    }
+
+    /// `self` to the power of `exponent`, by repeated squaring.
+    pub fn pow(&self, mut exponent: u32) -> Self {
+        let (mut base, mut result) = (self.clone(), Self::from(1u8));
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = &result * &base;
+            }
+            base = &base * &base;
+            exponent >>= 1;
+        }
+        result
+    }
+
+    /// The absolute value, if it fits in a `u128`.
+    pub fn magnitude_u128(&self) -> Option<u128> {
+        if self.magnitude.len() > 4 {
+            return None;
+        }
+        Some(
+            self.magnitude
+                .iter()
+                .rev()
+                .fold(0, |acc, &limb| acc << 32 | limb as u128),
+        )
+    }
+
+    pub fn to_u128(&self) -> Option<u128> {
+        self.magnitude_u128().filter(|_| !self.negative)
+    }
+
+    pub fn to_i128(&self) -> Option<i128> {
+        let magnitude = self.magnitude_u128()?;
+        if self.negative {
+            0i128.checked_sub_unsigned(magnitude)
+        } else {
+            i128::try_from(magnitude).ok()
+        }
+    }
+
+    fn from_magnitude(negative: bool, mut magnitude: u128) -> Self {
+        let mut limbs = Vec::with_capacity(4);
+        while magnitude > 0 {
+            limbs.push(magnitude as u32);
+            magnitude >>= 32;
+        }
+        Self::new(negative, limbs)
+    }
+
+    /// Quotient rounded towards zero and remainder with the sign of `self`, as
+    /// for the primitive integers.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `divisor` is zero.
+    pub fn div_rem(&self, divisor: &Self) -> (Self, Self) {
+        assert!(!divisor.is_zero(), "attempt to divide by zero");
+        let (quotient, remainder) = divide(&self.magnitude, &divisor.magnitude);
+        (
+            Self::new(self.negative != divisor.negative, quotient),
+            Self::new(self.negative, remainder),
+        )
+    }
+}
+
+fn compare(a: &[u32], b: &[u32]) -> Ordering {
+    a.len()
+        .cmp(&b.len())
+        .then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+fn add(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let (long, short) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    let mut sum = Vec::with_capacity(long.len() + 1);
+    let mut carry = 0u64;
+    for (i, &limb) in long.iter().enumerate() {
+        let total = limb as u64 + *short.get(i).unwrap_or(&0) as u64 + carry;
+        sum.push(total as u32);
+        carry = total >> 32;
+    }
+    sum.push(carry as u32);
+    sum
+}
+
+/// `a - b` for `a >= b`.
+fn subtract(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut difference = Vec::with_capacity(a.len());
+    let mut borrow = 0i64;
+    for (i, &limb) in a.iter().enumerate() {
+        let mut total = limb as i64 - *b.get(i).unwrap_or(&0) as i64 - borrow;
+        borrow = (total < 0) as i64;
+        total += borrow << 32;
+        difference.push(total as u32);
+    }
+    difference
+}
+
+fn multiply(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut product = vec![0u32; a.len() + b.len()];
+    for (i, &x) in a.iter().enumerate() {
+        let mut carry = 0u64;
+        for (j, &y) in b.iter().enumerate() {
+            let total = product[i + j] as u64 + x as u64 * y as u64 + carry;
+            product[i + j] = total as u32;
+            carry = total >> 32;
+        }
+        product[i + b.len()] = carry as u32;
+    }
+    product
+}
+
+/// Quotient and remainder of non-zero `b`, limb by limb for a single limb
+/// divisor and bit by bit otherwise.
+fn divide(a: &[u32], b: &[u32]) -> (Vec<u32>, Vec<u32>) {
+    if let [divisor] = b {
+        let mut quotient = vec![0; a.len()];
+        let mut remainder = 0u64;
+        for (i, &limb) in a.iter().enumerate().rev() {
+            let current = remainder << 32 | limb as u64;
+            quotient[i] = (current / *divisor as u64) as u32;
+            remainder = current % *divisor as u64;
+        }
+        return (quotient, vec![remainder as u32]);
+    }
+
+    let mut quotient = vec![0; a.len()];
+    let mut remainder: Vec<u32> = Vec::new();
+    for bit in (0..a.len() * 32).rev() {
+        // remainder = remainder * 2 + the next bit of a
+        let mut carry = a[bit / 32] >> (bit % 32) & 1;
+        for limb in remainder.iter_mut() {
+            let shifted = *limb >> 31;
+            *limb = *limb << 1 | carry;
+            carry = shifted;
+        }
+        if carry != 0 {
+            remainder.push(carry);
+        }
+        if compare(&remainder, b) != Ordering::Less {
+            remainder = subtract(&remainder, b);
+            while remainder.last() == Some(&0) {
+                remainder.pop();
+            }
+            quotient[bit / 32] |= 1 << (bit % 32);
+        }
+    }
+    (quotient, remainder)
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => compare(&self.magnitude, &other.magnitude),
+            (true, true) => compare(&other.magnitude, &self.magnitude),
+        }
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Neg for BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> BigInt {
+        let negative = !self.negative;
+        BigInt::new(negative, self.magnitude)
+    }
+}
+
+impl Add for &BigInt {
+    type Output = BigInt;
+
+    fn add(self, other: &BigInt) -> BigInt {
+        if self.negative == other.negative {
+            return BigInt::new(self.negative, add(&self.magnitude, &other.magnitude));
+        }
+        match compare(&self.magnitude, &other.magnitude) {
+            Ordering::Less => {
+                BigInt::new(other.negative, subtract(&other.magnitude, &self.magnitude))
+            }
+            _ => BigInt::new(self.negative, subtract(&self.magnitude, &other.magnitude)),
+        }
+    }
+}
+
+impl Sub for &BigInt {
+    type Output = BigInt;
+
+    fn sub(self, other: &BigInt) -> BigInt {
+        self + &-other.clone()
+    }
+}
+
+impl Mul for &BigInt {
+    type Output = BigInt;
+
+    fn mul(self, other: &BigInt) -> BigInt {
+        BigInt::new(
+            self.negative != other.negative,
+            multiply(&self.magnitude, &other.magnitude),
+        )
+    }
+}
+
+impl Div for &BigInt {
+    type Output = BigInt;
+
+    fn div(self, other: &BigInt) -> BigInt {
+        self.div_rem(other).0
+    }
+}
+
+impl Rem for &BigInt {
+    type Output = BigInt;
+
+    fn rem(self, other: &BigInt) -> BigInt {
+        self.div_rem(other).1
+    }
+}
+
+macro_rules! impl_owned_op {
+    ($trait_:ident, $method:ident) => {
+        impl $trait_ for BigInt {
+            type Output = BigInt;
+
+            fn $method(self, other: BigInt) -> BigInt {
+                (&self).$method(&other)
+            }
+        }
+    };
+}
+
+impl_owned_op!(Add, add);
+impl_owned_op!(Sub, sub);
+impl_owned_op!(Mul, mul);
+impl_owned_op!(Div, div);
+impl_owned_op!(Rem, rem);
+
+macro_rules! impl_from {
+    ($($type_:ident),*) => {
+        $(impl From<$type_> for BigInt {
+            fn from(x: $type_) -> Self {
+                Self::from_magnitude(x < 0, (x as i128).unsigned_abs())
+            }
+        })*
+    };
+}
+
+impl_from!(i8, i16, i32, i64, i128, isize);
+
+macro_rules! impl_from_unsigned {
+    ($($type_:ident),*) => {
+        $(impl From<$type_> for BigInt {
+            fn from(x: $type_) -> Self {
+                Self::from_magnitude(false, x as u128)
+            }
+        })*
+    };
+}
+
+impl_from_unsigned!(u8, u16, u32, u64, u128, usize);
+
+const DECIMAL_CHUNK: u32 = 1_000_000_000;
+
+impl Display for BigInt {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.negative {
+            write!(f, "-")?;
+        }
+        let mut chunks = Vec::new();
+        let mut rest = self.magnitude.clone();
+        while !rest.is_empty() {
+            let (quotient, remainder) = divide(&rest, &[DECIMAL_CHUNK]);
+            chunks.push(remainder[0]);
+            rest = BigInt::new(false, quotient).magnitude;
+        }
+        match chunks.split_last() {
+            None => write!(f, "0"),
+            Some((first, rest)) => {
+                write!(f, "{first}")?;
+                rest.iter()
+                    .rev()
+                    .try_for_each(|chunk| write!(f, "{chunk:09}"))
+            }
+        }
+    }
+}
+
+/// Reads an optionally signed decimal integer.
+impl FromStr for BigInt {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (negative, digits) = match s.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, s.strip_prefix('+').unwrap_or(s)),
+        };
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(ParseError);
+        }
+        let mut magnitude = Vec::new();
+        let head = digits.len() % 9;
+        let chunks = (head > 0).then(|| &digits[..head]).into_iter().chain(
+            digits.as_bytes()[head..]
+                .chunks(9)
+                .map(|c| std::str::from_utf8(c).unwrap()),
+        );
+        for chunk in chunks {
+            let scale = 10u32.pow(chunk.len() as u32);
+            magnitude = add(&multiply(&magnitude, &[scale]), &[chunk.parse().unwrap()]);
+        }
+        Ok(Self::new(negative, magnitude))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for BigInt {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// Big integers serialize as decimal strings, which JSON readers keep exact.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for BigInt {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = <String as serde::Deserialize>::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// Integer that is an `i128` as long as it fits and a `BigInt` beyond, with
+/// arithmetic that checks for overflow and falls back to `BigInt`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Int {
+    Small(i128),
+    Big(BigInt),
+}
+
+impl Int {
+    fn from_big(big: BigInt) -> Self {
+        match big.to_i128() {
+            Some(small) => Int::Small(small),
+            None => Int::Big(big),
+        }
+    }
+
+    pub fn to_big(&self) -> BigInt {
+        match self {
+            Int::Small(x) => BigInt::from(*x),
+            Int::Big(x) => x.clone(),
+        }
+    }
+}
+
+macro_rules! impl_checked_op {
+    ($trait_:ident, $method:ident, $checked:ident) => {
+        impl $trait_ for Int {
+            type Output = Int;
+
+            fn $method(self, other: Int) -> Int {
+                if let (Int::Small(a), Int::Small(b)) = (&self, &other) {
+                    if let Some(result) = a.$checked(*b) {
+                        return Int::Small(result);
+                    }
+                }
+                Int::from_big(self.to_big().$method(other.to_big()))
+            }
+        }
+    };
+}
+
+impl_checked_op!(Add, add, checked_add);
+impl_checked_op!(Sub, sub, checked_sub);
+impl_checked_op!(Mul, mul, checked_mul);
+impl_checked_op!(Div, div, checked_div);
+impl_checked_op!(Rem, rem, checked_rem);
+
+impl Ord for Int {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Int::Small(a), Int::Small(b)) => a.cmp(b),
+            _ => self.to_big().cmp(&other.to_big()),
+        }
+    }
+}
+
+impl PartialOrd for Int {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Display for Int {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Int::Small(x) => x.fmt(f),
+            Int::Big(x) => x.fmt(f),
+        }
+    }
+}
+
+macro_rules! impl_int_from {
+    ($($type_:ident),*) => {
+        $(impl From<$type_> for Int {
+            fn from(x: $type_) -> Self {
+                Int::from_big(BigInt::from(x))
+            }
+        })*
+    };
+}
+
+impl_int_from!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, BigInt);
+
+impl From<Int> for BigInt {
+    fn from(x: Int) -> Self {
+        x.to_big()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn big(s: &str) -> BigInt {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn factorial() {
+        let factorial = (1..=30u32).fold(BigInt::from(1u8), |acc, n| acc * BigInt::from(n));
+        assert_eq!(factorial.to_string(), "265252859812191058636308480000000");
+        assert_eq!(
+            &factorial / &(1..=29u32).fold(BigInt::from(1u8), |acc, n| acc * BigInt::from(n)),
+            BigInt::from(30u8)
+        );
+        assert_eq!(
+            BigInt::from(2u8).pow(200).to_string(),
+            "1606938044258990275541962092341162602522202993782792835301376"
+        );
+    }
+
+    #[test]
+    fn parse_and_print() {
+        for s in [
+            "0",
+            "-1",
+            "999999999",
+            "1000000000",
+            "-340282366920938463463374607431768211456",
+        ] {
+            assert_eq!(big(s).to_string(), s);
+        }
+        assert_eq!(big("-0"), BigInt::zero());
+        assert_eq!(big("+007").to_string(), "7");
+        assert_eq!("".parse::<BigInt>(), Err(ParseError));
+        assert_eq!("1e5".parse::<BigInt>(), Err(ParseError));
+        assert_eq!(
+            big("340282366920938463463374607431768211455").to_u128(),
+            Some(u128::MAX)
+        );
+        assert_eq!(
+            big("-170141183460469231731687303715884105728").to_i128(),
+            Some(i128::MIN)
+        );
+        assert_eq!(
+            big("170141183460469231731687303715884105728").to_i128(),
+            None
+        );
+    }
+
+    #[test]
+    fn checked_arithmetic_falls_back() {
+        let max = Int::from(i128::MAX);
+        assert_eq!(max.clone() - Int::from(1u8), Int::Small(i128::MAX - 1));
+        let sum = max.clone() + Int::from(1u8);
+        assert!(matches!(sum, Int::Big(_)));
+        assert_eq!(sum.to_string(), "170141183460469231731687303715884105728");
+        assert_eq!(sum - Int::from(1u8), max);
+        assert_eq!(
+            Int::from(i128::MIN) / Int::from(-1),
+            Int::from(i128::MIN).to_big().neg().into()
+        );
+        assert!(Int::from(u128::MAX) > max);
+    }
+
+    proptest! {
+        #[test]
+        fn agrees_with_i128(a in any::<i64>(), b in any::<i64>()) {
+            let (x, y) = (a as i128, b as i128);
+            let (big_x, big_y) = (BigInt::from(a), BigInt::from(b));
+            prop_assert_eq!((&big_x + &big_y).to_i128(), Some(x + y));
+            prop_assert_eq!((&big_x - &big_y).to_i128(), Some(x - y));
+            prop_assert_eq!((&big_x * &big_y).to_i128(), Some(x * y));
+            prop_assert_eq!(big_x.cmp(&big_y), x.cmp(&y));
+            if b != 0 {
+                prop_assert_eq!((&big_x / &big_y).to_i128(), Some(x / y));
+                prop_assert_eq!((&big_x % &big_y).to_i128(), Some(x % y));
+            }
+        }
+
+        #[test]
+        fn division_inverts_multiplication(a in any::<u128>(), b in 1..u128::MAX, r in any::<u64>()) {
+            let (a, b) = (BigInt::from(a), BigInt::from(b));
+            let r = BigInt::from(r) % b.clone();
+            let product = &(&a * &b) + &r;
+            prop_assert_eq!(product.div_rem(&b), (a, r));
+        }
+    }
+}
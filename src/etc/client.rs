@@ -0,0 +1,358 @@
+use std::env;
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use regex::Regex;
+
+use crate::etc::answers::Hint;
+
+pub const YEAR: u16 = 2022;
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const USER_AGENT: &str = "github.com/ingwinlu/aoc2022";
+const MIN_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Debug)]
+pub enum Error {
+    NoSession,
+    Throttled(Duration),
+    Http(u16, String),
+    Transport(String),
+    UnknownResponse(String),
+    Io(io::Error),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::NoSession => write!(
+                f,
+                "no session cookie, set AOC_SESSION or write it to .session"
+            ),
+            Error::Throttled(wait) => write!(f, "throttled, try again in {}s", wait.as_secs()),
+            Error::Http(status, body) => write!(f, "HTTP {status}: {}", body.trim()),
+            Error::Transport(error) => write!(f, "request failed: {error}"),
+            Error::UnknownResponse(page) => write!(f, "unrecognised response: {}", page.trim()),
+            Error::Io(error) => error.fmt(f),
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Error::Io(error)
+    }
+}
+
+impl From<ureq::Error> for Error {
+    fn from(error: ureq::Error) -> Self {
+        match error {
+            ureq::Error::Status(status, response) => {
+                Error::Http(status, response.into_string().unwrap_or_default())
+            }
+            ureq::Error::Transport(transport) => Error::Transport(transport.to_string()),
+        }
+    }
+}
+
+/// What AoC said about a submitted answer.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Incorrect(Hint),
+    Wait(Duration),
+    AlreadySolved,
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::Incorrect(hint) => write!(f, "incorrect, {hint}"),
+            Verdict::Wait(wait) => write!(f, "answered too recently, wait {}s", wait.as_secs()),
+            Verdict::AlreadySolved => write!(f, "already solved"),
+        }
+    }
+}
+
+/// Blocking client for puzzle inputs and answer submission.
+///
+/// Inputs are cached in `input_dir` and never requested twice. Requests are
+/// spaced at least `min_interval` apart and wait times reported by AoC are
+/// honoured, both tracked in `state_dir` so they survive between runs.
+pub struct Client {
+    pub base_url: String,
+    pub session: String,
+    pub input_dir: PathBuf,
+    pub state_dir: PathBuf,
+    pub min_interval: Duration,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_owned(),
+            session: session.trim().to_owned(),
+            input_dir: PathBuf::from("input"),
+            state_dir: PathBuf::from(".aoc"),
+            min_interval: MIN_INTERVAL,
+        }
+    }
+
+    /// Reads the session from `AOC_SESSION` or `.session` and the base URL from `AOC_BASE_URL`.
+    pub fn from_env() -> Result<Self, Error> {
+        let session = env::var("AOC_SESSION")
+            .or_else(|_| fs::read_to_string(".session"))
+            .map_err(|_| Error::NoSession)?;
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.to_owned());
+        Ok(Self::new(&base_url, &session))
+    }
+
+    pub fn input_path(&self, day: u8) -> PathBuf {
+        self.input_dir.join(format!("day{day:02}.txt"))
+    }
+
+    pub fn fetch_input(&self, day: u8) -> Result<String, Error> {
+        let path = self.input_path(day);
+        if let Ok(input) = fs::read_to_string(&path) {
+            return Ok(input);
+        }
+
+        self.throttle()?;
+        let url = format!("{}/{YEAR}/day/{day}/input", self.base_url);
+        let response = self.request("GET", &url).call();
+        self.wait_at_least(self.min_interval)?;
+        let input = response?.into_string()?;
+
+        fs::create_dir_all(&self.input_dir)?;
+        fs::write(&path, &input)?;
+        Ok(input)
+    }
+
+    pub fn submit(&self, day: u8, part: u8, answer: &str) -> Result<Verdict, Error> {
+        self.throttle()?;
+        let url = format!("{}/{YEAR}/day/{day}/answer", self.base_url);
+        let level = part.to_string();
+        let response = self
+            .request("POST", &url)
+            .send_form(&[("level", level.as_str()), ("answer", answer.trim())]);
+        self.wait_at_least(self.min_interval)?;
+        let page = response?.into_string()?;
+        let verdict = parse_verdict(&page).ok_or(Error::UnknownResponse(page.clone()))?;
+
+        match verdict {
+            Verdict::Wait(wait) => self.wait_at_least(wait)?,
+            Verdict::Incorrect(_) => {
+                self.wait_at_least(parse_cooldown(&page).max(self.min_interval))?
+            }
+            _ => {}
+        }
+        Ok(verdict)
+    }
+
+    fn request(&self, method: &str, url: &str) -> ureq::Request {
+        ureq::request(method, url)
+            .set("Cookie", &format!("session={}", self.session))
+            .set("User-Agent", USER_AGENT)
+    }
+
+    fn throttle(&self) -> Result<(), Error> {
+        let next = fs::read_to_string(self.state_dir.join("next_request"))
+            .ok()
+            .and_then(|s| s.trim().parse::<u64>().ok())
+            .unwrap_or(0);
+        let now = unix_now();
+        if now < next {
+            return Err(Error::Throttled(Duration::from_secs(next - now)));
+        }
+        Ok(())
+    }
+
+    fn wait_at_least(&self, wait: Duration) -> Result<(), Error> {
+        fs::create_dir_all(&self.state_dir)?;
+        let next = unix_now() + wait.as_secs();
+        fs::write(self.state_dir.join("next_request"), next.to_string())?;
+        Ok(())
+    }
+}
+
+pub fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn wait_re() -> &'static Regex {
+    static WAIT_RE: OnceLock<Regex> = OnceLock::new();
+    WAIT_RE.get_or_init(|| {
+        Regex::new(r"You have (?:(?P<min>\d+)m )?(?P<sec>\d+)s left to wait").unwrap()
+    })
+}
+
+fn cooldown_re() -> &'static Regex {
+    static COOLDOWN_RE: OnceLock<Regex> = OnceLock::new();
+    COOLDOWN_RE.get_or_init(|| {
+        Regex::new(r"(?i)please wait (?P<min>one|\d+) minutes? before trying again").unwrap()
+    })
+}
+
+fn parse_verdict(page: &str) -> Option<Verdict> {
+    if page.contains("That's the right answer") {
+        Some(Verdict::Correct)
+    } else if page.contains("That's not the right answer") {
+        if page.contains("your answer is too high") {
+            Some(Verdict::Incorrect(Hint::TooHigh))
+        } else if page.contains("your answer is too low") {
+            Some(Verdict::Incorrect(Hint::TooLow))
+        } else {
+            Some(Verdict::Incorrect(Hint::Unknown))
+        }
+    } else if let Some(captures) = wait_re().captures(page) {
+        let minutes: u64 = captures
+            .name("min")
+            .map_or(0, |m| m.as_str().parse().unwrap());
+        let seconds: u64 = captures["sec"].parse().unwrap();
+        Some(Verdict::Wait(Duration::from_secs(minutes * 60 + seconds)))
+    } else if page.contains("You don't seem to be solving the right level") {
+        Some(Verdict::AlreadySolved)
+    } else {
+        None
+    }
+}
+
+/// Lockout AoC imposes after an incorrect answer.
+fn parse_cooldown(page: &str) -> Duration {
+    let minutes = cooldown_re()
+        .captures(page)
+        .map_or(0, |captures| match &captures["min"] {
+            "one" => 1,
+            n => n.parse().unwrap(),
+        });
+    Duration::from_secs(minutes * 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    /// Serves one canned response per connection and hands back the raw requests.
+    fn mock_server(
+        responses: Vec<(u16, &'static str)>,
+    ) -> (String, thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            let mut requests = Vec::new();
+            for (status, body) in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request = String::new();
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some(len) = line.to_lowercase().strip_prefix("content-length:") {
+                        content_length = len.trim().parse().unwrap();
+                    }
+                    request.push_str(&line);
+                    if line == "\r\n" {
+                        break;
+                    }
+                }
+                let mut body_in = vec![0; content_length];
+                reader.read_exact(&mut body_in).unwrap();
+                request.push_str(&String::from_utf8(body_in).unwrap());
+                requests.push(request);
+
+                write!(
+                    stream,
+                    "HTTP/1.1 {status} X\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+            }
+            requests
+        });
+        (url, handle)
+    }
+
+    fn client(url: &str, name: &str) -> Client {
+        let dir = env::temp_dir().join(format!("aoc-client-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let mut client = Client::new(url, "secret");
+        client.input_dir = dir.join("input");
+        client.state_dir = dir.join("state");
+        client.min_interval = Duration::ZERO;
+        client
+    }
+
+    #[test]
+    fn fetch_input_is_cached() {
+        let (url, server) = mock_server(vec![(200, "1\n2\n3\n")]);
+        let client = client(&url, "fetch");
+
+        assert_eq!(client.fetch_input(1).unwrap(), "1\n2\n3\n");
+        assert_eq!(client.fetch_input(1).unwrap(), "1\n2\n3\n");
+
+        let requests = server.join().unwrap();
+        assert_eq!(requests.len(), 1);
+        assert!(requests[0].starts_with("GET /2022/day/1/input "));
+        assert!(requests[0].contains("session=secret"));
+    }
+
+    #[test]
+    fn submit_parses_verdicts() {
+        let (url, server) = mock_server(vec![
+            (200, "<article><p>That's not the right answer; your answer is too high.</p></article>"),
+            (200, "<article><p>That's not the right answer; your answer is too low. Please wait one minute before trying again.</p></article>"),
+            (200, "<article><p>That's the right answer! You are one gold star closer.</p></article>"),
+            (200, "<article><p>You gave an answer too recently. You have 1m 5s left to wait.</p></article>"),
+        ]);
+        let client = client(&url, "submit");
+
+        assert_eq!(
+            client.submit(3, 2, "42").unwrap(),
+            Verdict::Incorrect(Hint::TooHigh)
+        );
+        assert_eq!(
+            client.submit(3, 2, "12").unwrap(),
+            Verdict::Incorrect(Hint::TooLow)
+        );
+        assert!(matches!(
+            client.submit(3, 2, "13"),
+            Err(Error::Throttled(_))
+        ));
+        fs::remove_dir_all(&client.state_dir).unwrap();
+        assert_eq!(client.submit(3, 2, "41").unwrap(), Verdict::Correct);
+        assert_eq!(
+            client.submit(3, 2, "41").unwrap(),
+            Verdict::Wait(Duration::from_secs(65))
+        );
+        assert!(matches!(
+            client.submit(3, 2, "41"),
+            Err(Error::Throttled(_))
+        ));
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("POST /2022/day/3/answer "));
+        assert!(requests[0].ends_with("level=2&answer=42"));
+    }
+
+    #[test]
+    fn http_errors_are_reported() {
+        let (url, server) =
+            mock_server(vec![(404, "Please don't repeatedly request this endpoint")]);
+        let client = client(&url, "error");
+
+        assert!(matches!(client.fetch_input(25), Err(Error::Http(404, _))));
+        assert!(!client.input_path(25).exists());
+        server.join().unwrap();
+    }
+}
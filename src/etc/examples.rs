@@ -0,0 +1,179 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+use crate::etc::answers::AnswerLog;
+use crate::{Day, SolutionPair};
+
+/// Example input of a day together with the answers the puzzle text gives for it.
+///
+/// Stored in `input/examples/` as `dayNN.txt`, an optional `dayNN-2.txt` when
+/// part 2 comes with its own example, and the expected answers in
+/// `dayNN.answers` using the answers log format.
+#[derive(Clone, Debug, Default)]
+pub struct Example {
+    pub part1: String,
+    pub part2: Option<String>,
+    pub expected: AnswerLog,
+}
+
+impl Example {
+    fn path(day: u8, suffix: &str) -> PathBuf {
+        PathBuf::from(format!("input/examples/day{day:02}{suffix}"))
+    }
+
+    pub fn load(day: u8) -> Option<Self> {
+        let part1 = fs::read_to_string(Self::path(day, ".txt")).ok()?;
+        let part2 = fs::read_to_string(Self::path(day, "-2.txt")).ok();
+        let expected = AnswerLog::load_from(&Self::path(day, ".answers"))
+            .unwrap_or_else(|e| panic!("Malformed example answers: {e}"));
+        Some(Self {
+            part1,
+            part2,
+            expected,
+        })
+    }
+
+    pub fn save(&self, day: u8) -> io::Result<()> {
+        fs::create_dir_all("input/examples")?;
+        fs::write(Self::path(day, ".txt"), &self.part1)?;
+        if let Some(part2) = &self.part2 {
+            fs::write(Self::path(day, "-2.txt"), part2)?;
+        }
+        fs::write(Self::path(day, ".answers"), self.expected.to_string())
+    }
+
+    pub fn input(&self, part: u8) -> &str {
+        match (part, &self.part2) {
+            (2, Some(part2)) => part2,
+            _ => &self.part1,
+        }
+    }
+
+    /// Runs each part of `day` on its example.
+    pub fn solve(&self, day: &Day) -> SolutionPair {
+        ((day.part1)(self.input(1)), (day.part2)(self.input(2)))
+    }
+
+    /// Extracts the example from a saved puzzle page.
+    ///
+    /// Each part's input is the first `<pre><code>` block of its article and its
+    /// answer the last emphasised `<code>`, which is where AoC states the result.
+    /// Part 2 falls back to part 1's example when its article has no block.
+    pub fn extract(page: &str) -> Option<Self> {
+        let mut example = Self::default();
+        for (part, article) in (1..=2).zip(article_re().captures_iter(page)) {
+            let article = &article[1];
+            let block = pre_re().captures(article).map(|c| unescape(&c[1]));
+            match (part, block) {
+                (1, Some(block)) => example.part1 = block,
+                (1, None) => return None,
+                (_, block) => example.part2 = block.filter(|b| *b != example.part1),
+            }
+            if let Some(answer) = answer_re().captures_iter(article).last() {
+                let answer = answer.get(1).or_else(|| answer.get(2)).unwrap();
+                example.expected.accept(part, &unescape(answer.as_str()));
+            }
+        }
+        Some(example).filter(|example| !example.part1.is_empty())
+    }
+}
+
+fn article_re() -> &'static Regex {
+    static ARTICLE_RE: OnceLock<Regex> = OnceLock::new();
+    ARTICLE_RE
+        .get_or_init(|| Regex::new(r#"(?s)<article class="day-desc">(.*?)</article>"#).unwrap())
+}
+
+fn pre_re() -> &'static Regex {
+    static PRE_RE: OnceLock<Regex> = OnceLock::new();
+    PRE_RE.get_or_init(|| Regex::new(r"(?s)<pre><code>(.*?)</code></pre>").unwrap())
+}
+
+fn answer_re() -> &'static Regex {
+    static ANSWER_RE: OnceLock<Regex> = OnceLock::new();
+    ANSWER_RE.get_or_init(|| {
+        Regex::new(r"(?s)<code><em>(.*?)</em></code>|<em><code>(.*?)</code></em>").unwrap()
+    })
+}
+
+fn tag_re() -> &'static Regex {
+    static TAG_RE: OnceLock<Regex> = OnceLock::new();
+    TAG_RE.get_or_init(|| Regex::new(r"<[^>]*>").unwrap())
+}
+
+fn unescape(html: &str) -> String {
+    tag_re()
+        .replace_all(html, "")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::etc::answers::Check;
+    use crate::{get_day, Solution};
+
+    const PAGE: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 9: Rope Bridge ---</h2><p>Consider:</p>
+<pre><code>R 4
+U 4
+</code></pre>
+<p>Count <code>&lt;T&gt;</code> where it is <code>1</code>: <code><em>13</em></code>.</p>
+</article>
+<p>Your puzzle answer was <code>6354</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>A larger example:</p>
+<pre><code><em>R 5</em>
+U 8
+</code></pre>
+<p>Now it visits <em><code>36</code></em> positions.</p>
+</article>
+</main>"#;
+
+    #[test]
+    fn extract_saved_page() {
+        let example = Example::extract(PAGE).unwrap();
+        assert_eq!(example.part1, "R 4\nU 4\n");
+        assert_eq!(example.part2.as_deref(), Some("R 5\nU 8\n"));
+        assert_eq!(
+            example.expected.check(1, &Solution::from(13u64)),
+            Check::Correct
+        );
+        assert_eq!(
+            example.expected.check(2, &Solution::from(36u64)),
+            Check::Correct
+        );
+    }
+
+    #[test]
+    fn extract_without_example() {
+        assert!(
+            Example::extract("<article class=\"day-desc\"><p>No blocks</p></article>").is_none()
+        );
+        assert!(Example::extract("<html></html>").is_none());
+    }
+
+    #[test]
+    fn examples_match_expected_answers() {
+        for day in 1..=25 {
+            let example = match Example::load(day) {
+                Some(example) => example,
+                None => continue,
+            };
+            let (p1, p2) = example.solve(&get_day(day).unwrap());
+            for (part, solution) in [(1, p1), (2, p2)] {
+                match example.expected.check(part, &solution) {
+                    Check::Correct | Check::Unknown => {}
+                    check => panic!("Day {day:02} part {part}: {solution} {check}"),
+                }
+            }
+        }
+    }
+}
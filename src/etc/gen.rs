@@ -0,0 +1,465 @@
+//! Random but valid puzzle inputs for stress tests and benchmarks at scale.
+//!
+//! `generate(day, seed, size)` is deterministic in `seed`, and `size` scales the
+//! input roughly linearly: it is the number of lines, groups, pairs or
+//! directories of the day, or the width of its map.
+
+use std::fmt::Write;
+use std::ops::RangeInclusive;
+
+use crate::days::day13::Packet;
+
+/// Small deterministic random number generator (xorshift64*).
+#[derive(Clone, Debug)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        // splitmix64 of the seed, so that small seeds start far apart and 0 works
+        let mut z = seed.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        Self((z ^ (z >> 31)) | 1)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    /// Uniform value in `0..n`.
+    pub fn below(&mut self, n: u64) -> u64 {
+        self.next_u64() % n
+    }
+
+    /// Uniform value in `range`.
+    pub fn range(&mut self, range: RangeInclusive<i64>) -> i64 {
+        let (start, end) = range.into_inner();
+        start + self.below((end - start + 1) as u64) as i64
+    }
+
+    /// `true` with probability `1 / n`.
+    pub fn one_in(&mut self, n: u64) -> bool {
+        self.below(n) == 0
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len() as u64) as usize]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i as u64 + 1) as usize);
+        }
+    }
+}
+
+/// Days `generate` has an input generator for.
+pub const DAYS: RangeInclusive<u8> = 1..=14;
+
+/// Random input of `day`, or `None` if there is no generator for it.
+pub fn generate(day: u8, seed: u64, size: usize) -> Option<String> {
+    let rng = &mut Rng::new(seed);
+    let size = size.max(1);
+    let input = match day {
+        1 => calories(rng, size),
+        2 => strategy_guide(rng, size),
+        3 => rucksacks(rng, size),
+        4 => section_pairs(rng, size),
+        5 => crate_stacks(rng, size),
+        6 => datastream(rng, size),
+        7 => terminal_transcript(rng, size),
+        8 => tree_heights(rng, size),
+        9 => rope_moves(rng, size),
+        10 => cpu_program(rng, size),
+        11 => monkey_notes(rng, size),
+        12 => heightmap(rng, size),
+        13 => packet_pairs(rng, size),
+        14 => rock_paths(rng, size),
+        _ => return None,
+    };
+    Some(input)
+}
+
+/// `size` elves carrying one to six snacks each.
+fn calories(rng: &mut Rng, size: usize) -> String {
+    let elves: Vec<String> = (0..size)
+        .map(|_| {
+            let snacks: Vec<String> = (0..rng.range(1..=6))
+                .map(|_| rng.range(1000..=60_000).to_string())
+                .collect();
+            snacks.join("\n")
+        })
+        .collect();
+    elves.join("\n\n") + "\n"
+}
+
+/// `size` rounds of rock paper scissors.
+fn strategy_guide(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            format!(
+                "{} {}\n",
+                rng.choose(&['A', 'B', 'C']),
+                rng.choose(&['X', 'Y', 'Z'])
+            )
+        })
+        .collect()
+}
+
+/// `size` groups of three rucksacks. The compartments of a rucksack share
+/// exactly one item type and the rucksacks of a group exactly one badge.
+fn rucksacks(rng: &mut Rng, size: usize) -> String {
+    let mut items: Vec<char> = ('a'..='z').chain('A'..='Z').collect();
+    let mut out = String::new();
+    for _ in 0..size {
+        rng.shuffle(&mut items);
+        let (badge, others) = items.split_first().unwrap();
+        // 17 item types of its own per rucksack: 8 for each compartment and a shared one
+        for pool in others.chunks(17) {
+            let (shared, pool) = pool.split_first().unwrap();
+            let (first, second) = pool.split_at(8);
+            let half = rng.range(2..=12) as usize;
+            let mut compartments = [vec![*shared, *badge], vec![*shared]];
+            for (compartment, pool) in compartments.iter_mut().zip([first, second]) {
+                while compartment.len() < half {
+                    compartment.push(*rng.choose(pool));
+                }
+                rng.shuffle(compartment);
+            }
+            let [first, second] = compartments;
+            out.extend(first.into_iter().chain(second));
+            out.push('\n');
+        }
+    }
+    out
+}
+
+/// `size` pairs of section assignments.
+fn section_pairs(rng: &mut Rng, size: usize) -> String {
+    let mut out = String::new();
+    for _ in 0..size {
+        let [a, b, c, d] = [(); 4].map(|_| rng.range(1..=99));
+        let (a, b) = (a.min(b), a.max(b));
+        let (c, d) = (c.min(d), c.max(d));
+        writeln!(out, "{a}-{b},{c}-{d}").unwrap();
+    }
+    out
+}
+
+/// Up to nine stacks of crates followed by `size` moves of crates that exist.
+fn crate_stacks(rng: &mut Rng, size: usize) -> String {
+    let mut stacks: Vec<Vec<char>> = (0..rng.range(3..=9))
+        .map(|_| {
+            (0..rng.range(1..=8))
+                .map(|_| (b'A' + rng.below(26) as u8) as char)
+                .collect()
+        })
+        .collect();
+
+    let mut out = String::new();
+    let height = stacks.iter().map(Vec::len).max().unwrap();
+    for level in (0..height).rev() {
+        let row: Vec<String> = stacks
+            .iter()
+            .map(|stack| match stack.get(level) {
+                Some(c) => format!("[{c}]"),
+                None => "   ".to_owned(),
+            })
+            .collect();
+        writeln!(out, "{}", row.join(" ")).unwrap();
+    }
+    let labels: Vec<String> = (1..=stacks.len()).map(|i| format!(" {i} ")).collect();
+    writeln!(out, "{}\n", labels.join(" ")).unwrap();
+
+    for _ in 0..size {
+        let from = loop {
+            let from = rng.below(stacks.len() as u64) as usize;
+            if !stacks[from].is_empty() {
+                break from;
+            }
+        };
+        let to = (from + 1 + rng.below(stacks.len() as u64 - 1) as usize) % stacks.len();
+        let amount = rng.range(1..=stacks[from].len() as i64) as usize;
+        let keep = stacks[from].len() - amount;
+        let moved = stacks[from].split_off(keep);
+        stacks[to].extend(moved);
+        writeln!(out, "move {amount} from {} to {}", from + 1, to + 1).unwrap();
+    }
+    out
+}
+
+/// `size` characters drawn from 13 letters, so that they cannot hold a
+/// start-of-message marker, followed by one and some more noise.
+fn datastream(rng: &mut Rng, size: usize) -> String {
+    let mut out: String = (0..size)
+        .map(|_| (b'a' + rng.below(13) as u8) as char)
+        .collect();
+    let mut marker: Vec<char> = ('a'..='z').collect();
+    rng.shuffle(&mut marker);
+    out.extend(&marker[..14]);
+    out.extend((0..rng.below(20)).map(|_| (b'a' + rng.below(26) as u8) as char));
+    out.push('\n');
+    out
+}
+
+/// Transcript of `cd` and `ls` exploring `size` directories depth first.
+/// A filler file in `/` brings the disk usage to between 40M and 70M, as the
+/// puzzle needs to have enough to free up.
+fn terminal_transcript(rng: &mut Rng, size: usize) -> String {
+    // children of every directory, directory 0 being `/`
+    let mut children: Vec<Vec<usize>> = vec![Vec::new()];
+    for dir in 1..size {
+        let parent = rng.below(dir as u64) as usize;
+        children[parent].push(dir);
+        children.push(Vec::new());
+    }
+    let files: Vec<u64> = (0..size).map(|_| rng.below(5)).collect();
+    let max_file = (35_000_000 / (files.iter().sum::<u64>() + 1)).min(300_000);
+
+    let mut total = 0;
+    let mut listings = Vec::with_capacity(size);
+    for (dir, &count) in files.iter().enumerate() {
+        let mut listing = String::new();
+        for child in &children[dir] {
+            writeln!(listing, "dir d{child}").unwrap();
+        }
+        for file in 0..count {
+            let file_size = 1 + rng.below(max_file);
+            total += file_size;
+            let extension = rng.choose(&["", ".txt", ".dat", ".lst"]);
+            writeln!(listing, "{file_size} f{file}{extension}").unwrap();
+        }
+        listings.push(listing);
+    }
+    let filler = rng.range(41_000_000..=69_000_000) as u64 - total;
+    listings[0].push_str(&format!("{filler} filler\n"));
+
+    fn explore(dir: usize, children: &[Vec<usize>], listings: &[String], out: &mut String) {
+        out.push_str("$ ls\n");
+        out.push_str(&listings[dir]);
+        for &child in &children[dir] {
+            writeln!(out, "$ cd d{child}").unwrap();
+            explore(child, children, listings, out);
+            out.push_str("$ cd ..\n");
+        }
+    }
+    let mut out = "$ cd /\n".to_owned();
+    explore(0, &children, &listings, &mut out);
+    out
+}
+
+/// `size` by `size` grid of tree heights.
+fn tree_heights(rng: &mut Rng, size: usize) -> String {
+    let mut out = String::with_capacity(size * (size + 1));
+    for _ in 0..size {
+        out.extend((0..size).map(|_| (b'0' + rng.below(10) as u8) as char));
+        out.push('\n');
+    }
+    out
+}
+
+/// `size` head motions of up to 20 steps.
+fn rope_moves(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            format!(
+                "{} {}\n",
+                rng.choose(&['R', 'U', 'L', 'D']),
+                rng.range(1..=20)
+            )
+        })
+        .collect()
+}
+
+/// At least `size` instructions, and enough to last the 240 cycles of the CRT,
+/// keeping the register within the 40 columns.
+fn cpu_program(rng: &mut Rng, size: usize) -> String {
+    let (mut x, mut cycles, mut instructions) = (1, 0, 0);
+    let mut out = String::new();
+    while instructions < size || cycles < 240 {
+        if rng.one_in(3) {
+            out.push_str("noop\n");
+            cycles += 1;
+        } else {
+            let target = rng.range(0..=39);
+            if target == x {
+                continue;
+            }
+            writeln!(out, "addx {}", target - x).unwrap();
+            x = target;
+            cycles += 2;
+        }
+        instructions += 1;
+    }
+    out
+}
+
+/// Notes on `size` monkeys, at most nine as each one divides by its own prime.
+///
+/// Monkey 0 only throws to later monkeys and all others only to earlier ones,
+/// so that an item is inspected at most twice per round, and there is no
+/// `old * old`: worry levels of part 1 stay in range for all 20 rounds.
+fn monkey_notes(rng: &mut Rng, size: usize) -> String {
+    let mut primes = [2, 3, 5, 7, 11, 13, 17, 19, 23];
+    rng.shuffle(&mut primes);
+    let count = size.clamp(2, primes.len());
+
+    let mut notes = Vec::with_capacity(count);
+    for (monkey, prime) in primes.iter().enumerate().take(count) {
+        let items: Vec<String> = (0..rng.range(1..=6))
+            .map(|_| rng.range(50..=99).to_string())
+            .collect();
+        let operation = match rng.below(3) {
+            0 => format!("* {}", rng.range(2..=7)),
+            1 => format!("+ {}", rng.range(1..=8)),
+            _ => "+ old".to_owned(),
+        };
+        let target = |rng: &mut Rng| match monkey {
+            0 => rng.range(1..=count as i64 - 1),
+            _ => rng.range(0..=monkey as i64 - 1),
+        };
+        let (if_true, if_false) = (target(rng), target(rng));
+        notes.push(format!(
+            "Monkey {monkey}:\n  Starting items: {}\n  Operation: new = old {operation}\n  Test: divisible by {prime}\n    If true: throw to monkey {if_true}\n    If false: throw to monkey {if_false}\n",
+            items.join(", ")
+        ));
+    }
+    notes.join("\n")
+}
+
+/// `size` wide heightmap, but at least 40 so that there is room for the climb,
+/// rising from `S` towards `E` and scattered with pits. Cells on an L-shaped
+/// path from `S` to `E` are never pits, so that there is a way up.
+fn heightmap(rng: &mut Rng, size: usize) -> String {
+    let width = size.max(40) as i64;
+    let height = width * 2 / 3;
+    let end = (rng.range(0..=width - 1), rng.range(0..=height - 1));
+    let start = (
+        if end.0 < width / 2 { width - 1 } else { 0 },
+        if end.1 < height / 2 { height - 1 } else { 0 },
+    );
+    let slope = rng.range(1..=3);
+    let on_path = |(x, y): (i64, i64)| {
+        (y == start.1 && (x - start.0) * (x - end.0) <= 0)
+            || (x == end.0 && (y - start.1) * (y - end.1) <= 0)
+    };
+
+    let mut out = String::new();
+    for y in 0..height {
+        for x in 0..width {
+            let c = if (x, y) == start {
+                'S'
+            } else if (x, y) == end {
+                'E'
+            } else {
+                let from_start = (x - start.0).abs() + (y - start.1).abs();
+                let to_end = (x - end.0).abs() + (y - end.1).abs();
+                let mut elevation = (25 - (to_end - 1) / slope).clamp(0, from_start);
+                if !on_path((x, y)) && rng.one_in(6) {
+                    elevation = (elevation - 2).max(0);
+                }
+                (b'a' + elevation as u8) as char
+            };
+            out.push(c);
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// `size` pairs of random packets, which, as in real inputs, are lists at the
+/// top level, never compare equal and are never in the place of a divider
+/// packet.
+fn packet_pairs(rng: &mut Rng, size: usize) -> String {
+    fn packet(rng: &mut Rng, depth: u32) -> String {
+        if depth == 0 || rng.one_in(3) {
+            return rng.range(0..=10).to_string();
+        }
+        let items: Vec<String> = (0..rng.below(5)).map(|_| packet(rng, depth - 1)).collect();
+        format!("[{}]", items.join(","))
+    }
+
+    let dividers = ["[[2]]", "[[6]]"].map(|divider| Packet::parse_one(divider).0);
+    let pairs: Vec<String> = (0..size)
+        .map(|_| loop {
+            let left = format!("[{}]", packet(rng, 4));
+            let right = format!("[{}]", packet(rng, 4));
+            let (left_packet, right_packet) =
+                (Packet::parse_one(&left).0, Packet::parse_one(&right).0);
+            if left_packet != right_packet
+                && !dividers.contains(&left_packet)
+                && !dividers.contains(&right_packet)
+            {
+                break format!("{left}\n{right}");
+            }
+        })
+        .collect();
+    pairs.join("\n\n") + "\n"
+}
+
+/// `size` paths of rock below the sand source at `500,0`.
+///
+/// The rock starts further down than it spreads out sideways, so that it cannot
+/// hold a pile of sand reaching up to the source: as in real inputs, the sand
+/// of part 1 ends up falling into the abyss.
+fn rock_paths(rng: &mut Rng, size: usize) -> String {
+    let spread = 2 + size as i64 / 4;
+    let (top, bottom) = (spread + 3, spread + 13 + size as i64 / 2);
+    let mut out = String::new();
+    for _ in 0..size {
+        let mut point = (
+            rng.range(500 - spread..=500 + spread),
+            rng.range(top..=bottom),
+        );
+        let mut points = vec![format!("{},{}", point.0, point.1)];
+        for _ in 0..rng.range(1..=4) {
+            let step = rng.range(1..=6) * if rng.one_in(2) { 1 } else { -1 };
+            if rng.one_in(2) {
+                point.0 = (point.0 + step).clamp(500 - spread, 500 + spread);
+            } else {
+                point.1 = (point.1 + step).clamp(top, bottom);
+            }
+            points.push(format!("{},{}", point.0, point.1));
+        }
+        writeln!(out, "{}", points.join(" -> ")).unwrap();
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::get_day_solver;
+
+    #[test]
+    fn generated_inputs_are_solvable() {
+        for day in DAYS {
+            let solver = get_day_solver(day).unwrap();
+            for seed in 0..3 {
+                let input = generate(day, seed, 30).unwrap();
+                let result = std::panic::catch_unwind(|| solver(&input));
+                assert!(result.is_ok(), "day {day} seed {seed}:\n{input}");
+            }
+        }
+    }
+
+    #[test]
+    fn generation_is_deterministic() {
+        assert_eq!(generate(11, 7, 5), generate(11, 7, 5));
+        assert_ne!(generate(11, 7, 5), generate(11, 8, 5));
+        assert_eq!(generate(25, 7, 5), None);
+    }
+
+    #[test]
+    fn valid_rucksacks() {
+        let input = generate(3, 1, 10).unwrap();
+        for line in input.lines() {
+            let (first, second) = line.split_at(line.len() / 2);
+            let shared: Vec<char> = first.chars().filter(|&c| second.contains(c)).collect();
+            assert!(shared.windows(2).all(|pair| pair[0] == pair[1]), "{line}");
+        }
+    }
+}
@@ -0,0 +1,328 @@
+//! Points in the plane and in space with signed integer coordinates of any
+//! width, and the boxes bounding them.
+//!
+//! Rotations take `y` as pointing up. On maps where `y` grows downwards, such as
+//! [`Grid`](crate::etc::grid::Grid), they turn the other way round on screen.
+
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// Signed integer types points can have as coordinates.
+pub trait Coordinate:
+    Copy
+    + Debug
+    + Default
+    + Ord
+    + Hash
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Neg<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn abs(self) -> Self;
+
+    fn signum(self) -> Self;
+}
+
+macro_rules! impl_coordinate {
+    ($($t:ty),*) => {$(
+        impl Coordinate for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn abs(self) -> Self {
+                <$t>::abs(self)
+            }
+
+            fn signum(self) -> Self {
+                <$t>::signum(self)
+            }
+        }
+    )*};
+}
+
+impl_coordinate!(i8, i16, i32, i64, i128, isize);
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+/// Operations on points one axis at a time, as bounding boxes need them.
+pub trait Componentwise: Copy + Add<Output = Self> + Sub<Output = Self> {
+    /// Point with all coordinates one.
+    const ONES: Self;
+
+    /// Point with the smaller of both coordinates on each axis.
+    fn component_min(self, other: Self) -> Self;
+
+    /// Point with the larger of both coordinates on each axis.
+    fn component_max(self, other: Self) -> Self;
+
+    /// Whether no coordinate is larger than the one of `other`.
+    fn all_le(self, other: Self) -> bool;
+}
+
+/// Implements what both kinds of points have in common, one coordinate at a
+/// time.
+macro_rules! impl_point {
+    ($point:ident { $($c:ident),* }) => {
+        impl<T: Coordinate> $point<T> {
+            pub const ORIGIN: Self = Self { $($c: T::ZERO),* };
+
+            /// Length of the shortest path to `other` along the axes.
+            pub fn manhattan(self, other: Self) -> T {
+                T::ZERO $(+ (self.$c - other.$c).abs())*
+            }
+
+            /// Number of king's moves to `other`, with diagonal steps allowed.
+            pub fn chebyshev(self, other: Self) -> T {
+                T::ZERO $(.max((self.$c - other.$c).abs()))*
+            }
+
+            /// Step of at most one along each axis in the direction of the point.
+            pub fn signum(self) -> Self {
+                Self { $($c: self.$c.signum()),* }
+            }
+        }
+
+        impl<T: Coordinate> Componentwise for $point<T> {
+            const ONES: Self = Self { $($c: T::ONE),* };
+
+            fn component_min(self, other: Self) -> Self {
+                Self { $($c: self.$c.min(other.$c)),* }
+            }
+
+            fn component_max(self, other: Self) -> Self {
+                Self { $($c: self.$c.max(other.$c)),* }
+            }
+
+            fn all_le(self, other: Self) -> bool {
+                true $(&& self.$c <= other.$c)*
+            }
+        }
+
+        impl<T: Coordinate> Add for $point<T> {
+            type Output = Self;
+
+            fn add(self, other: Self) -> Self {
+                Self { $($c: self.$c + other.$c),* }
+            }
+        }
+
+        impl<T: Coordinate> Sub for $point<T> {
+            type Output = Self;
+
+            fn sub(self, other: Self) -> Self {
+                Self { $($c: self.$c - other.$c),* }
+            }
+        }
+
+        impl<T: Coordinate> AddAssign for $point<T> {
+            fn add_assign(&mut self, other: Self) {
+                *self = *self + other;
+            }
+        }
+
+        impl<T: Coordinate> SubAssign for $point<T> {
+            fn sub_assign(&mut self, other: Self) {
+                *self = *self - other;
+            }
+        }
+
+        impl<T: Coordinate> Neg for $point<T> {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                Self { $($c: -self.$c),* }
+            }
+        }
+
+        /// Scaling by a factor.
+        impl<T: Coordinate> Mul<T> for $point<T> {
+            type Output = Self;
+
+            fn mul(self, factor: T) -> Self {
+                Self { $($c: self.$c * factor),* }
+            }
+        }
+    };
+}
+
+impl_point!(Point2 { x, y });
+impl_point!(Point3 { x, y, z });
+
+impl<T> Point2<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T: Coordinate> Point2<T> {
+    /// Quarter turn counter-clockwise around the origin.
+    pub fn rotate_left(self) -> Self {
+        Self::new(-self.y, self.x)
+    }
+
+    /// Quarter turn clockwise around the origin.
+    pub fn rotate_right(self) -> Self {
+        Self::new(self.y, -self.x)
+    }
+}
+
+impl<T> From<(T, T)> for Point2<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Self::new(x, y)
+    }
+}
+
+impl<T> Point3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+}
+
+/// Quarter turns counter-clockwise around each axis, looking at the origin from
+/// the positive end of the axis.
+impl<T: Coordinate> Point3<T> {
+    pub fn rotate_x(self) -> Self {
+        Self::new(self.x, -self.z, self.y)
+    }
+
+    pub fn rotate_y(self) -> Self {
+        Self::new(self.z, self.y, -self.x)
+    }
+
+    pub fn rotate_z(self) -> Self {
+        Self::new(-self.y, self.x, self.z)
+    }
+}
+
+impl<T> From<(T, T, T)> for Point3<T> {
+    fn from((x, y, z): (T, T, T)) -> Self {
+        Self::new(x, y, z)
+    }
+}
+
+/// Box from `min` to `max`, both included, aligned with the axes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct BoundingBox<P> {
+    pub min: P,
+    pub max: P,
+}
+
+impl<P: Componentwise> BoundingBox<P> {
+    /// Smallest box around `points`, or `None` if there are none.
+    pub fn of(points: impl IntoIterator<Item = P>) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        let mut bounds = Self {
+            min: first,
+            max: first,
+        };
+        points.for_each(|point| bounds.include(point));
+        Some(bounds)
+    }
+
+    /// Grows the box to contain `point`.
+    pub fn include(&mut self, point: P) {
+        self.min = self.min.component_min(point);
+        self.max = self.max.component_max(point);
+    }
+
+    pub fn contains(&self, point: P) -> bool {
+        self.min.all_le(point) && point.all_le(self.max)
+    }
+
+    /// Number of points the box spans along each axis.
+    pub fn size(&self) -> P {
+        self.max - self.min + P::ONES
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn arithmetic() {
+        let mut point = Point2::new(3, -4);
+        assert_eq!(point + Point2::new(1, 1), Point2::new(4, -3));
+        assert_eq!(-point * 2, Point2::new(-6, 8));
+        point -= Point2::new(3, 0);
+        assert_eq!(point, Point2::new(0, -4));
+        assert_eq!(Point3::new(2, -7, 0).signum(), Point3::new(1, -1, 0));
+    }
+
+    #[test]
+    fn distances() {
+        let (a, b) = (Point2::new(1i64, 5), Point2::new(4, 1));
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(a.chebyshev(b), 4);
+        let c = Point3::new(-1i8, 2, 3);
+        assert_eq!(c.manhattan(Point3::ORIGIN), 6);
+        assert_eq!(c.chebyshev(Point3::ORIGIN), 3);
+    }
+
+    #[test]
+    fn rotations() {
+        let east = Point2::new(1, 0);
+        assert_eq!(east.rotate_left(), Point2::new(0, 1));
+        assert_eq!(east.rotate_right(), Point2::new(0, -1));
+        let point = Point3::new(1, 2, 3);
+        assert_eq!(point.rotate_x(), Point3::new(1, -3, 2));
+        assert_eq!(point.rotate_y(), Point3::new(3, 2, -1));
+        assert_eq!(point.rotate_z(), Point3::new(-2, 1, 3));
+    }
+
+    #[test]
+    fn bounding_boxes() {
+        let points = [(2, 3), (-1, 5), (0, 0)].map(Point2::from);
+        let bounds = BoundingBox::of(points).unwrap();
+        assert_eq!(bounds.min, Point2::new(-1, 0));
+        assert_eq!(bounds.max, Point2::new(2, 5));
+        assert_eq!(bounds.size(), Point2::new(4, 6));
+        assert!(bounds.contains(Point2::new(0, 5)));
+        assert!(!bounds.contains(Point2::new(3, 5)));
+        assert_eq!(BoundingBox::<Point3<i32>>::of([]), None);
+    }
+
+    fn point3() -> impl Strategy<Value = Point3<i64>> {
+        (-1000..1000i64, -1000..1000i64, -1000..1000i64).prop_map(Point3::from)
+    }
+
+    proptest! {
+        #[test]
+        fn four_turns_go_round(point in point3()) {
+            let turn_4 = |turn: fn(Point3<i64>) -> Point3<i64>| (0..4).fold(point, |p, _| turn(p));
+            prop_assert_eq!(turn_4(Point3::rotate_x), point);
+            prop_assert_eq!(turn_4(Point3::rotate_y), point);
+            prop_assert_eq!(turn_4(Point3::rotate_z), point);
+            let flat = Point2::new(point.x, point.y);
+            prop_assert_eq!(flat.rotate_left().rotate_right(), flat);
+            prop_assert_eq!(point.rotate_z().manhattan(Point3::ORIGIN), point.manhattan(Point3::ORIGIN));
+        }
+
+        #[test]
+        fn distances_are_bounded(a in point3(), b in point3()) {
+            prop_assert!(a.chebyshev(b) <= a.manhattan(b));
+            prop_assert!(a.manhattan(b) <= 3 * a.chebyshev(b));
+            prop_assert_eq!((a - b).signum().chebyshev(Point3::ORIGIN), (a != b) as i64);
+            let bounds = BoundingBox::of([a, b]).unwrap();
+            prop_assert!(bounds.contains(a) && bounds.contains(b));
+        }
+    }
+}
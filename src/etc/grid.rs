@@ -0,0 +1,316 @@
+//! Rectangular 2D maps with their cells stored row by row in a single `Vec`.
+//!
+//! Cells are addressed by `(x, y)` points, with `x` growing to the right and `y`
+//! downwards from `(0, 0)` at the top left. Points outside the grid are
+//! allowed everywhere and read as missing, so that walks can run off the map.
+
+use std::fmt::{self, Display, Formatter};
+use std::ops::{Index, IndexMut};
+
+use crate::etc::geom::Point2;
+
+/// `(x, y)` position of a cell.
+pub type Point = Point2<i64>;
+
+/// Steps to the four orthogonal neighbours: up, left, right and down.
+pub const ORTHOGONAL: [Point; 4] = [
+    Point::new(0, -1),
+    Point::new(-1, 0),
+    Point::new(1, 0),
+    Point::new(0, 1),
+];
+
+/// Steps to all eight neighbours, in reading order.
+pub const ALL_DIRECTIONS: [Point; 8] = [
+    Point::new(-1, -1),
+    Point::new(0, -1),
+    Point::new(1, -1),
+    Point::new(-1, 0),
+    Point::new(1, 0),
+    Point::new(-1, 1),
+    Point::new(0, 1),
+    Point::new(1, 1),
+];
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Grid of `width` times `height` cells, each `value`.
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Grid of the lines of `input`, with each character turned into a cell by
+    /// `cell`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the lines are not all as long as the first one.
+    pub fn parse(input: &str, mut cell: impl FnMut(char) -> T) -> Self {
+        let mut width = None;
+        let mut cells = Vec::with_capacity(input.len());
+        let mut height = 0;
+        for line in input.lines() {
+            let before = cells.len();
+            cells.extend(line.chars().map(&mut cell));
+            let line_width = cells.len() - before;
+            assert_eq!(
+                *width.get_or_insert(line_width),
+                line_width,
+                "line {} is not as long as the first one",
+                height + 1
+            );
+            height += 1;
+        }
+        Self {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        (0..self.width as i64).contains(&point.x) && (0..self.height as i64).contains(&point.y)
+    }
+
+    fn offset(&self, point: Point) -> Option<usize> {
+        self.contains(point)
+            .then(|| point.y as usize * self.width + point.x as usize)
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.offset(point).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.offset(point).map(|i| &mut self.cells[i])
+    }
+
+    /// Sets the cell at `point` to `value`, returning whether it is in the grid.
+    pub fn set(&mut self, point: Point, value: T) -> bool {
+        match self.get_mut(point) {
+            Some(cell) => {
+                *cell = value;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// All points of the grid, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width as i64;
+        (0..self.height as i64).flat_map(move |y| (0..width).map(move |x| Point::new(x, y)))
+    }
+
+    /// All cells with their points, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(&self.cells)
+    }
+
+    /// All cells, row by row.
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    /// Point of the first cell, row by row, for which `predicate` holds.
+    pub fn position(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<Point> {
+        self.iter()
+            .find(|(_, cell)| predicate(cell))
+            .map(|(point, _)| point)
+    }
+
+    /// Grid of the same size with the cells turned into `f` of their point and
+    /// value.
+    pub fn map<U>(&self, mut f: impl FnMut(Point, &T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.iter().map(|(point, cell)| f(point, cell)).collect(),
+        }
+    }
+
+    /// Cells of row `y`, from left to right.
+    pub fn row(&self, y: usize) -> impl Iterator<Item = &T> {
+        assert!(
+            y < self.height,
+            "row {y} is outside the {}x{} grid",
+            self.width,
+            self.height
+        );
+        self.cells[y * self.width..(y + 1) * self.width].iter()
+    }
+
+    /// Cells of column `x`, from top to bottom.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(
+            x < self.width,
+            "column {x} is outside the {}x{} grid",
+            self.width,
+            self.height
+        );
+        self.cells[x..].iter().step_by(self.width)
+    }
+
+    /// Cells from `start` in steps of `step`, leaving out `start` itself, until
+    /// the edge of the grid.
+    pub fn ray(&self, start: Point, step: Point) -> impl Iterator<Item = (Point, &T)> + Clone {
+        let mut point = start;
+        std::iter::from_fn(move || {
+            point += step;
+            self.get(point).map(|cell| (point, cell))
+        })
+    }
+
+    fn neighbours<'a>(
+        &'a self,
+        point: Point,
+        steps: &'a [Point],
+    ) -> impl Iterator<Item = (Point, &'a T)> {
+        steps.iter().filter_map(move |step| {
+            let neighbour = point + *step;
+            self.get(neighbour).map(|cell| (neighbour, cell))
+        })
+    }
+
+    /// The up to four orthogonal neighbours of `point` within the grid.
+    pub fn neighbours4(&self, point: Point) -> impl Iterator<Item = (Point, &T)> {
+        self.neighbours(point, &ORTHOGONAL)
+    }
+
+    /// The up to eight neighbours of `point` within the grid, diagonals included.
+    pub fn neighbours8(&self, point: Point) -> impl Iterator<Item = (Point, &T)> {
+        self.neighbours(point, &ALL_DIRECTIONS)
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &T {
+        self.get(point).unwrap_or_else(|| {
+            panic!(
+                "{point:?} is outside the {}x{} grid",
+                self.width, self.height
+            )
+        })
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(point)
+            .unwrap_or_else(|| panic!("{point:?} is outside the {width}x{height} grid"))
+    }
+}
+
+/// The cells row by row, each row on a line of its own.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for y in 0..self.height {
+            self.row(y).try_for_each(|cell| cell.fmt(f))?;
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    fn digits() -> Grid<u32> {
+        Grid::parse("123\n456\n", |c| c.to_digit(10).unwrap())
+    }
+
+    #[test]
+    fn parse_and_display() {
+        let input = indoc! {"
+            #..
+            .#.
+        "};
+        let grid = Grid::parse(input, |c| c == '#');
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get(Point::new(1, 1)), Some(&true));
+        assert_eq!(grid.get(Point::new(3, 0)), None);
+        assert_eq!(
+            grid.map(|_, &lit| if lit { '#' } else { '.' }).to_string(),
+            input
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "line 2 is not as long as the first one")]
+    fn parse_ragged() {
+        Grid::parse("12\n3\n", |c| c);
+    }
+
+    #[test]
+    #[should_panic(expected = "row 2 is outside the 3x2 grid")]
+    fn row_outside() {
+        digits().row(2).count();
+    }
+
+    #[test]
+    #[should_panic(expected = "column 3 is outside the 3x2 grid")]
+    fn column_outside() {
+        digits().column(3).count();
+    }
+
+    #[test]
+    fn get_and_set() {
+        let mut grid = digits();
+        assert!(grid.set(Point::new(2, 1), 0));
+        assert!(!grid.set(Point::new(-1, 0), 0));
+        grid[Point::new(0, 0)] = 9;
+        assert_eq!(grid.to_string(), "923\n450\n");
+        assert_eq!(grid.position(|&digit| digit == 5), Some(Point::new(1, 1)));
+        assert_eq!(Grid::new(2, 1, 'x').to_string(), "xx\n");
+    }
+
+    #[test]
+    fn iterate() {
+        let grid = digits();
+        assert_eq!(grid.row(1).copied().collect::<Vec<_>>(), [4, 5, 6]);
+        assert_eq!(grid.column(2).copied().collect::<Vec<_>>(), [3, 6]);
+        assert_eq!(
+            grid.ray(Point::new(0, 0), Point::new(1, 1))
+                .collect::<Vec<_>>(),
+            [(Point::new(1, 1), &5)]
+        );
+        assert_eq!(grid.ray(Point::new(2, 1), Point::new(-1, 0)).count(), 2);
+        assert_eq!(grid.iter().nth(4), Some((Point::new(1, 1), &5)));
+        let neighbours: Vec<u32> = grid
+            .neighbours4(Point::new(0, 0))
+            .map(|(_, &d)| d)
+            .collect();
+        assert_eq!(neighbours, [2, 4]);
+        let neighbours: Vec<u32> = grid
+            .neighbours8(Point::new(1, 0))
+            .map(|(_, &d)| d)
+            .collect();
+        assert_eq!(neighbours, [1, 3, 4, 5, 6]);
+    }
+}
@@ -0,0 +1,267 @@
+//! Intervals of integers and sets of them, worked with by their ends instead of
+//! by every number in them.
+//!
+//! Intervals are half-open, from `start` up to but not including `end`.
+//! [`Interval::closed`] makes one from its first and last number instead.
+
+use std::ops::{Add, Range, RangeInclusive, Sub};
+
+/// Integer types intervals can range over.
+pub trait Endpoint: Copy + Ord + Add<Output = Self> + Sub<Output = Self> {
+    const ZERO: Self;
+    const ONE: Self;
+}
+
+macro_rules! impl_endpoint {
+    ($($t:ty),*) => {$(
+        impl Endpoint for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+        }
+    )*};
+}
+
+impl_endpoint!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// The numbers from `start` up to but not including `end`. It is empty if
+/// `end` is not after `start`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Interval<T> {
+    pub start: T,
+    pub end: T,
+}
+
+impl<T: Endpoint> Interval<T> {
+    pub fn new(start: T, end: T) -> Self {
+        Self { start, end }
+    }
+
+    /// The numbers from `first` to `last`, both included.
+    pub fn closed(first: T, last: T) -> Self {
+        Self::new(first, last + T::ONE)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.end <= self.start
+    }
+
+    /// Number of numbers in the interval.
+    pub fn len(&self) -> T {
+        if self.is_empty() {
+            T::ZERO
+        } else {
+            self.end - self.start
+        }
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.start <= value && value < self.end
+    }
+
+    /// Whether all numbers of the interval are in `other` as well.
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.is_empty() || (other.start <= self.start && self.end <= other.end)
+    }
+
+    /// Whether the intervals have a number in common.
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// Numbers in both intervals, or `None` if there are none.
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let both = Self::new(self.start.max(other.start), self.end.min(other.end));
+        (!both.is_empty()).then_some(both)
+    }
+}
+
+impl<T: Endpoint> From<Range<T>> for Interval<T> {
+    fn from(range: Range<T>) -> Self {
+        Self::new(range.start, range.end)
+    }
+}
+
+impl<T: Endpoint> From<RangeInclusive<T>> for Interval<T> {
+    fn from(range: RangeInclusive<T>) -> Self {
+        let (first, last) = range.into_inner();
+        Self::closed(first, last)
+    }
+}
+
+/// Set of numbers kept as the fewest intervals covering them, in order and with
+/// gaps between them.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct RangeSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T: Endpoint> RangeSet<T> {
+    pub fn new() -> Self {
+        Self {
+            intervals: Vec::new(),
+        }
+    }
+
+    /// Adds the numbers of `interval`, merging it with the intervals it
+    /// overlaps or touches.
+    pub fn insert(&mut self, interval: Interval<T>) {
+        if interval.is_empty() {
+            return;
+        }
+        let first = self
+            .intervals
+            .partition_point(|other| other.end < interval.start);
+        let last = self
+            .intervals
+            .partition_point(|other| other.start <= interval.end);
+        let mut merged = interval;
+        if first < last {
+            merged.start = merged.start.min(self.intervals[first].start);
+            merged.end = merged.end.max(self.intervals[last - 1].end);
+        }
+        self.intervals.splice(first..last, [merged]);
+    }
+
+    /// Takes out the numbers of `interval`, splitting an interval it falls in.
+    pub fn remove(&mut self, interval: Interval<T>) {
+        if interval.is_empty() {
+            return;
+        }
+        let first = self
+            .intervals
+            .partition_point(|other| other.end <= interval.start);
+        let last = self
+            .intervals
+            .partition_point(|other| other.start < interval.end);
+        let mut rest = Vec::new();
+        if first < last {
+            let (head, tail) = (self.intervals[first], self.intervals[last - 1]);
+            if head.start < interval.start {
+                rest.push(Interval::new(head.start, interval.start));
+            }
+            if interval.end < tail.end {
+                rest.push(Interval::new(interval.end, tail.end));
+            }
+        }
+        self.intervals.splice(first..last, rest);
+    }
+
+    /// Numbers in either set.
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = self.clone();
+        other.iter().for_each(|&interval| union.insert(interval));
+        union
+    }
+
+    /// Numbers in this set but not in `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut difference = self.clone();
+        other
+            .iter()
+            .for_each(|&interval| difference.remove(interval));
+        difference
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let index = self
+            .intervals
+            .partition_point(|interval| interval.end <= value);
+        self.intervals
+            .get(index)
+            .is_some_and(|interval| interval.contains(value))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Number of numbers in the set.
+    pub fn len(&self) -> T {
+        self.iter()
+            .fold(T::ZERO, |total, interval| total + interval.len())
+    }
+
+    /// The intervals making up the set, in order.
+    pub fn iter(&self) -> impl Iterator<Item = &Interval<T>> {
+        self.intervals.iter()
+    }
+}
+
+impl<T: Endpoint> FromIterator<Interval<T>> for RangeSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(intervals: I) -> Self {
+        let mut set = Self::new();
+        intervals
+            .into_iter()
+            .for_each(|interval| set.insert(interval));
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn intervals() {
+        let interval = Interval::closed(2, 4);
+        assert_eq!(interval, Interval::from(2..5));
+        assert_eq!(interval, Interval::from(2..=4));
+        assert_eq!(interval.len(), 3);
+        assert!(interval.contains(4) && !interval.contains(5));
+        assert!(Interval::closed(3, 4).is_subset(&interval));
+        assert!(!Interval::closed(3, 5).is_subset(&interval));
+        assert_eq!(
+            interval.intersection(&(4..9).into()),
+            Some(Interval::new(4, 5))
+        );
+        assert!(!interval.overlaps(&(5..9).into()));
+        assert_eq!(Interval::new(5u8, 2).len(), 0);
+    }
+
+    #[test]
+    fn range_sets() {
+        let mut set: RangeSet<i32> = [0..3, 10..12, 3..5]
+            .into_iter()
+            .map(Interval::from)
+            .collect();
+        let intervals =
+            |set: &RangeSet<i32>| set.iter().map(|i| i.start..i.end).collect::<Vec<_>>();
+        assert_eq!(intervals(&set), [0..5, 10..12]);
+        assert_eq!(set.len(), 7);
+        set.remove(Interval::closed(2, 10));
+        assert_eq!(intervals(&set), [0..2, 11..12]);
+        assert!(set.contains(11) && !set.contains(10));
+        let other = RangeSet::from_iter([Interval::new(1, 20)]);
+        assert_eq!(intervals(&set.union(&other)), vec![0..20]);
+        assert_eq!(intervals(&other.difference(&set)), [2..11, 12..20]);
+        assert!(set.difference(&set).is_empty());
+    }
+
+    fn intervals_strategy() -> impl Strategy<Value = Vec<Interval<i32>>> {
+        let interval = (-20..20, 0..10).prop_map(|(start, len)| Interval::new(start, start + len));
+        prop::collection::vec(interval, 0..8)
+    }
+
+    fn numbers(intervals: &[Interval<i32>]) -> BTreeSet<i32> {
+        intervals.iter().flat_map(|i| i.start..i.end).collect()
+    }
+
+    proptest! {
+        #[test]
+        fn range_sets_agree_with_sets(a in intervals_strategy(), b in intervals_strategy()) {
+            let (set_a, set_b) = (RangeSet::from_iter(a.clone()), RangeSet::from_iter(b.clone()));
+            let (numbers_a, numbers_b) = (numbers(&a), numbers(&b));
+            let as_numbers = |set: RangeSet<i32>| numbers(&set.iter().copied().collect::<Vec<_>>());
+
+            prop_assert_eq!(set_a.len() as usize, numbers_a.len());
+            prop_assert!(set_a.iter().zip(set_a.iter().skip(1)).all(|(x, y)| x.end < y.start));
+            prop_assert_eq!(as_numbers(set_a.union(&set_b)), &numbers_a | &numbers_b);
+            prop_assert_eq!(as_numbers(set_a.difference(&set_b)), &numbers_a - &numbers_b);
+            for value in -25..35 {
+                prop_assert_eq!(set_a.contains(value), numbers_a.contains(&value));
+            }
+        }
+    }
+}
@@ -0,0 +1,323 @@
+//! Delta debugging of failing inputs down to a small reproducer.
+
+use std::cell::RefCell;
+use std::fmt::{self, Display, Formatter};
+use std::panic;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+use crate::etc::reference::Reference;
+use crate::{get_day, Day, Solution};
+
+/// What running one part on an input came to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Answer(String),
+    /// Panicked, at the given `file:line:column`.
+    Panic(String),
+    Timeout,
+}
+
+/// The way a part fails on an input, which a smaller input has to reproduce.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Failure {
+    /// Panics, at the same location.
+    Panic { part: u8, location: String },
+    /// Runs for longer than the timeout.
+    Timeout { part: u8 },
+    /// Gets a different answer than the variant `name`.
+    Variant { part: u8, name: &'static str },
+    /// Gets a different answer than the reference script.
+    Reference { part: u8 },
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Failure::Panic { part, location } => write!(f, "part {part} panics at {location}"),
+            Failure::Timeout { part } => write!(f, "part {part} times out"),
+            Failure::Variant { part, name } => {
+                write!(f, "part {part} disagrees with its variant {name}")
+            }
+            Failure::Reference { part } => {
+                write!(f, "part {part} disagrees with the reference script")
+            }
+        }
+    }
+}
+
+impl Failure {
+    fn part(&self) -> u8 {
+        match self {
+            Failure::Panic { part, .. }
+            | Failure::Timeout { part }
+            | Failure::Variant { part, .. }
+            | Failure::Reference { part } => *part,
+        }
+    }
+}
+
+thread_local! {
+    static PANIC_LOCATION: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Runs `solve` on `input` in a thread of its own, giving up after `timeout`.
+///
+/// A solver that times out keeps running in the background, as threads cannot
+/// be stopped from the outside. Panics are only reported correctly while the
+/// hook of `quiet_panics` is installed.
+pub fn run(solve: fn(&str) -> Solution, input: &str, timeout: Duration) -> Outcome {
+    let (sender, receiver) = mpsc::channel();
+    let input = input.to_owned();
+    thread::spawn(move || {
+        let outcome = match panic::catch_unwind(|| solve(&input)) {
+            Ok(solution) => Outcome::Answer(solution.to_string()),
+            Err(_) => Outcome::Panic(
+                PANIC_LOCATION
+                    .with(|location| location.borrow_mut().take())
+                    .unwrap_or_default(),
+            ),
+        };
+        let _ = sender.send(outcome);
+    });
+    receiver.recv_timeout(timeout).unwrap_or(Outcome::Timeout)
+}
+
+/// Runs `f` with a panic hook recording where solvers panic instead of printing
+/// every panic of the many attempts.
+pub fn quiet_panics<T>(f: impl FnOnce() -> T) -> T {
+    let _restore = RestoreHook(Some(panic::take_hook()));
+    panic::set_hook(Box::new(|info| {
+        let location = info.location().map(ToString::to_string);
+        PANIC_LOCATION.with(|last| *last.borrow_mut() = location);
+    }));
+    f()
+}
+
+type Hook = Box<dyn Fn(&panic::PanicHookInfo<'_>) + Sync + Send + 'static>;
+
+/// Puts the previous panic hook back when dropped, even if `f` panics.
+struct RestoreHook(Option<Hook>);
+
+impl Drop for RestoreHook {
+    fn drop(&mut self) {
+        if let Some(previous) = self.0.take() {
+            panic::set_hook(previous);
+        }
+    }
+}
+
+/// Checks inputs of a day for failures and whether they still show them.
+pub struct Checker {
+    pub day: u8,
+    entry: Day,
+    reference: Option<Reference>,
+    timeout: Duration,
+}
+
+impl Checker {
+    pub fn new(day: u8, timeout: Duration) -> Option<Self> {
+        Some(Self {
+            day,
+            entry: get_day(day)?,
+            reference: Reference::find(day).filter(|_| Reference::python_available()),
+            timeout,
+        })
+    }
+
+    fn part(&self, part: u8) -> fn(&str) -> Solution {
+        if part == 1 {
+            self.entry.part1
+        } else {
+            self.entry.part2
+        }
+    }
+
+    /// First failure of either part on `input`.
+    pub fn detect(&self, input: &str) -> Option<Failure> {
+        let reference = self.reference.as_ref().and_then(|r| r.solve(input).ok());
+        for part in 1..=2 {
+            let answer = match run(self.part(part), input, self.timeout) {
+                Outcome::Answer(answer) => answer,
+                Outcome::Panic(location) => return Some(Failure::Panic { part, location }),
+                Outcome::Timeout => return Some(Failure::Timeout { part }),
+            };
+            for variant in &self.entry.variants(part)[1..] {
+                match run(variant.solve, input, self.timeout) {
+                    Outcome::Answer(other) if other == answer => {}
+                    _ => {
+                        return Some(Failure::Variant {
+                            part,
+                            name: variant.name,
+                        })
+                    }
+                }
+            }
+            if let Some(reference) = &reference {
+                if reference[part as usize - 1] != answer {
+                    return Some(Failure::Reference { part });
+                }
+            }
+        }
+        None
+    }
+
+    /// Whether `input` still fails the same way.
+    pub fn reproduces(&self, failure: &Failure, input: &str) -> bool {
+        let outcome = run(self.part(failure.part()), input, self.timeout);
+        match (failure, outcome) {
+            (Failure::Panic { location, .. }, Outcome::Panic(other)) => *location == other,
+            (Failure::Timeout { .. }, Outcome::Timeout) => true,
+            (Failure::Variant { part, name }, Outcome::Answer(answer)) => {
+                let variant = self.entry.variant(*part, name).expect("variant exists");
+                matches!(run(variant.solve, input, self.timeout), Outcome::Answer(other) if other != answer)
+            }
+            (Failure::Reference { part }, Outcome::Answer(answer)) => {
+                let reference = self.reference.as_ref().expect("reference exists");
+                matches!(reference.solve(input), Ok(expected) if expected[*part as usize - 1] != answer)
+            }
+            _ => false,
+        }
+    }
+
+    /// `failure` on `input` as a test to paste into the tests of the day's module.
+    pub fn test_case(&self, failure: &Failure, input: &str) -> String {
+        let part = failure.part();
+        let indented: String = input
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .lines()
+            .map(|line| match line {
+                "" => "\n".to_owned(),
+                line => format!("            {line}\n"),
+            })
+            .collect();
+        let (name, check) = match failure {
+            Failure::Panic { .. } => ("minimized", "        part(input);\n".to_owned()),
+            Failure::Timeout { .. } => (
+                "minimized",
+                format!(
+                    "        let outcome = crate::etc::minimize::run(part, input, std::time::Duration::from_millis({}));\n        assert_ne!(outcome, crate::etc::minimize::Outcome::Timeout);\n",
+                    self.timeout.as_millis()
+                ),
+            ),
+            Failure::Variant { name, .. } => (
+                "minimized_variant",
+                format!(
+                    "        let variant = crate::get_day({}).unwrap().variant({part}, \"{name}\").unwrap();\n        assert_eq!((variant.solve)(input).to_string(), part(input).to_string());\n",
+                    self.day
+                ),
+            ),
+            Failure::Reference { .. } => {
+                let expected = self
+                    .reference
+                    .as_ref()
+                    .and_then(|reference| reference.solve(input).ok())
+                    .map(|answers| answers[part as usize - 1].clone())
+                    .unwrap_or_default();
+                (
+                    "minimized_reference",
+                    format!("        assert_eq!(part(input).to_string(), \"{expected}\");\n"),
+                )
+            }
+        };
+        format!(
+            "    // {failure}\n    #[test]\n    fn {name}_day_{part}() {{\n        let input = indoc! {{\"\n{indented}        \"}};\n        let part = crate::get_day({}).unwrap().part{part};\n{check}    }}\n",
+            self.day
+        )
+    }
+}
+
+/// Smallest input `fails` still holds for, found by removing ever smaller
+/// chunks first of the blank-line separated blocks and then of the lines of
+/// `input` (ddmin, complements only).
+pub fn minimize(input: &str, mut fails: impl FnMut(&str) -> bool) -> String {
+    let trailing = if input.ends_with('\n') { "\n" } else { "" };
+    let blocks: Vec<&str> = input.trim_end_matches('\n').split("\n\n").collect();
+    let blocks = ddmin(blocks, |units| fails(&(units.join("\n\n") + trailing)));
+    let joined = blocks.join("\n\n");
+    let lines = ddmin(joined.split('\n').collect(), |units| {
+        fails(&(units.join("\n") + trailing))
+    });
+    lines.join("\n") + trailing
+}
+
+fn ddmin<'a>(mut units: Vec<&'a str>, mut fails: impl FnMut(&[&'a str]) -> bool) -> Vec<&'a str> {
+    let mut chunks = 2;
+    while units.len() >= 2 {
+        let size = units.len().div_ceil(chunks);
+        let reduced = (0..units.len()).step_by(size).find_map(|start| {
+            let mut candidate = units[..start].to_vec();
+            candidate.extend_from_slice(&units[(start + size).min(units.len())..]);
+            fails(&candidate).then_some(candidate)
+        });
+        match reduced {
+            Some(candidate) => {
+                units = candidate;
+                chunks = (chunks - 1).max(2);
+            }
+            None if chunks >= units.len() => break,
+            None => chunks = (chunks * 2).min(units.len()),
+        }
+    }
+    units
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    #[test]
+    fn minimize_lines() {
+        let input = "a\nb\nc\nd\ne\nf\ng\n";
+        let minimized = minimize(input, |input| {
+            input.contains("b\n") && input.contains("f\n")
+        });
+        assert_eq!(minimized, "b\nf\n");
+    }
+
+    #[test]
+    fn minimize_blocks() {
+        let input = "1\n2\n\n3\n4\n\n5\n";
+        let minimized = minimize(input, |input| input.contains("4"));
+        assert_eq!(minimized, "4\n");
+    }
+
+    #[test]
+    fn minimize_panic() {
+        let checker = Checker::new(4, Duration::from_secs(10)).unwrap();
+        let input = indoc! {"
+            2-4,6-8
+            2-3,4-5
+            5-7
+            2-8,3-7
+            6-6,4-6
+        "};
+        let (failure, minimized) = quiet_panics(|| {
+            let failure = checker.detect(input).unwrap();
+            let minimized = minimize(input, |input| checker.reproduces(&failure, input));
+            (failure, minimized)
+        });
+        assert!(matches!(failure, Failure::Panic { part: 1, .. }));
+        assert_eq!(minimized, "5-7\n");
+        assert!(checker
+            .test_case(&failure, &minimized)
+            .contains("let input = indoc! {\"\n            5-7\n        \"};\n        let part = crate::get_day(4).unwrap().part1;\n        part(input);\n"));
+    }
+
+    #[test]
+    fn timeouts_fail_instead_of_hanging() {
+        let checker = Checker::new(8, Duration::from_millis(1500)).unwrap();
+        let test_case = checker.test_case(&Failure::Timeout { part: 2 }, "1\n");
+        assert!(test_case.contains("let part = crate::get_day(8).unwrap().part2;\n"));
+        assert!(test_case.contains("run(part, input, std::time::Duration::from_millis(1500));\n"));
+    }
+
+    #[test]
+    fn nothing_to_minimize() {
+        let checker = Checker::new(4, Duration::from_secs(10)).unwrap();
+        assert_eq!(checker.detect("2-4,6-8\n"), None);
+    }
+}
@@ -1,3 +1,21 @@
+pub mod answers;
+pub mod bigint;
+pub mod client;
+pub mod examples;
+pub mod gen;
+pub mod geom;
+pub mod grid;
+pub mod intervals;
+mod json;
+pub mod minimize;
+pub mod ocr;
+pub mod progress;
+pub mod reference;
+pub mod results;
+pub mod search;
+pub mod server;
+pub mod snapshot;
 pub mod solution;
+pub mod trace;
 
-pub use solution::Solution;
\ No newline at end of file
+pub use solution::Solution;
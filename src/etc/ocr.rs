@@ -0,0 +1,221 @@
+//! Reading the capital letters AoC draws as `#`/`.` pictures, in its 4x6 font
+//! (as on the CRT of 2022 day 10) and its 6x10 font.
+//!
+//! Letters are told apart by the blank columns between them, so their width
+//! does not matter, only their height picks the font.
+
+use std::fmt::{self, Display, Formatter};
+
+/// Letters of the 4x6 font, each followed by one blank column on screen.
+const FONT_6: &[(char, [&str; 6])] = &[
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', [".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Y', ["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+/// Letters of the 6x10 font, each followed by two blank columns on screen.
+#[rustfmt::skip]
+const FONT_10: &[(char, [&str; 10])] = &[
+    ('A', ["..##..", ".#..#.", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#", "#....#"]),
+    ('B', ["#####.", "#....#", "#....#", "#....#", "#####.", "#....#", "#....#", "#....#", "#....#", "#####."]),
+    ('C', [".####.", "#....#", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#....#", ".####."]),
+    ('E', ["######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....", "#.....", "######"]),
+    ('F', ["######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....", "#.....", "#....."]),
+    ('G', [".####.", "#....#", "#.....", "#.....", "#.....", "#..###", "#....#", "#....#", "#...##", ".###.#"]),
+    ('H', ["#....#", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#", "#....#", "#....#"]),
+    ('J', ["...###", "....#.", "....#.", "....#.", "....#.", "....#.", "....#.", "#...#.", "#...#.", ".###.."]),
+    ('K', ["#....#", "#...#.", "#..#..", "#.#...", "##....", "##....", "#.#...", "#..#..", "#...#.", "#....#"]),
+    ('L', ["#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "######"]),
+    ('N', ["#....#", "##...#", "##...#", "#.#..#", "#.#..#", "#..#.#", "#..#.#", "#...##", "#...##", "#....#"]),
+    ('P', ["#####.", "#....#", "#....#", "#....#", "#####.", "#.....", "#.....", "#.....", "#.....", "#....."]),
+    ('R', ["#####.", "#....#", "#....#", "#....#", "#####.", "#..#..", "#...#.", "#...#.", "#....#", "#....#"]),
+    ('X', ["#....#", "#....#", ".#..#.", ".#..#.", "..##..", "..##..", ".#..#.", ".#..#.", "#....#", "#....#"]),
+    ('Z', ["######", ".....#", ".....#", "....#.", "...#..", "..#...", ".#....", "#.....", "#.....", "######"]),
+];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// No font is as high as the picture.
+    Height(usize),
+    /// The glyph starting at `column` is none of the font's letters.
+    UnknownGlyph { column: usize, glyph: Vec<String> },
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Height(height) => {
+                write!(f, "no font is {height} rows high, only 6 and 10 are known")
+            }
+            Error::UnknownGlyph { column, glyph } => {
+                write!(f, "unknown glyph at column {column}:")?;
+                glyph.iter().try_for_each(|row| write!(f, "\n{row}"))
+            }
+        }
+    }
+}
+
+/// The letters drawn by `rows`, with `#` for lit cells.
+pub fn decode<S: AsRef<str>>(rows: &[S]) -> Result<String, Error> {
+    let rows: Vec<&[u8]> = rows.iter().map(|row| row.as_ref().as_bytes()).collect();
+    let font: Vec<(char, Vec<&str>)> = match rows.len() {
+        6 => FONT_6
+            .iter()
+            .map(|(c, glyph)| (*c, glyph.to_vec()))
+            .collect(),
+        10 => FONT_10
+            .iter()
+            .map(|(c, glyph)| (*c, glyph.to_vec()))
+            .collect(),
+        height => return Err(Error::Height(height)),
+    };
+    let width = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+    let lit = |column: usize| rows.iter().any(|row| row.get(column) == Some(&b'#'));
+
+    let mut letters = String::new();
+    let mut column = 0;
+    while column < width {
+        if !lit(column) {
+            column += 1;
+            continue;
+        }
+        let start = column;
+        while column < width && lit(column) {
+            column += 1;
+        }
+        let glyph: Vec<String> = rows
+            .iter()
+            .map(|row| {
+                (start..column)
+                    .map(|c| if row.get(c) == Some(&b'#') { '#' } else { '.' })
+                    .collect()
+            })
+            .collect();
+        let letter = font
+            .iter()
+            .find(|(_, letter)| trim(letter) == glyph)
+            .ok_or(Error::UnknownGlyph {
+                column: start,
+                glyph,
+            })?;
+        letters.push(letter.0);
+    }
+    Ok(letters)
+}
+
+/// `glyph` without its blank columns on either side.
+fn trim(glyph: &[&str]) -> Vec<String> {
+    let lit = |column: usize| glyph.iter().any(|row| row.as_bytes()[column] == b'#');
+    let width = glyph[0].len();
+    let start = (0..width).find(|&c| lit(c)).unwrap_or(0);
+    let end = (0..width).rev().find(|&c| lit(c)).map_or(start, |c| c + 1);
+    glyph.iter().map(|row| row[start..end].to_owned()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    fn rows(picture: &str) -> Vec<&str> {
+        picture.lines().collect()
+    }
+
+    /// `text` drawn in the 4x6 font the way the CRT shows it, five columns per
+    /// letter, or `None` if it has a letter the font lacks.
+    fn render(text: &str) -> Option<Vec<String>> {
+        let mut rows = vec![String::new(); 6];
+        for c in text.chars() {
+            let (_, glyph) = FONT_6.iter().find(|(letter, _)| *letter == c)?;
+            for (row, line) in rows.iter_mut().zip(glyph) {
+                row.push_str(&format!("{line:.<5}"));
+            }
+        }
+        Some(rows)
+    }
+
+    /// Letters the 4x6 font can draw within five columns.
+    fn letters() -> impl Iterator<Item = char> {
+        FONT_6
+            .iter()
+            .filter(|(_, glyph)| glyph[0].len() < 5)
+            .map(|(letter, _)| *letter)
+    }
+
+    #[test]
+    fn decode_crt() {
+        let picture = indoc! {"
+            ###..####.#..#.####..##....##..##..###..
+            #..#....#.#..#.#....#..#....#.#..#.#..#.
+            #..#...#..####.###..#.......#.#....###..
+            ###...#...#..#.#....#.##....#.#....#..#.
+            #.#..#....#..#.#....#..#.#..#.#..#.#..#.
+            #..#.####.#..#.#.....###..##...##..###..
+        "};
+        assert_eq!(decode(&rows(picture)), Ok("RZHFGJCB".to_owned()));
+    }
+
+    #[test]
+    fn decode_large_letters() {
+        let picture = indoc! {"
+            #....#..#####...
+            #....#..#....#..
+            .#..#...#....#..
+            .#..#...#....#..
+            ..##....#####...
+            ..##....#.......
+            .#..#...#.......
+            .#..#...#.......
+            #....#..#.......
+            #....#..#.......
+        "};
+        assert_eq!(decode(&rows(picture)), Ok("XP".to_owned()));
+    }
+
+    #[test]
+    fn render_and_decode() {
+        let text: String = letters().collect();
+        assert_eq!(decode(&render(&text).unwrap()), Ok(text));
+        assert_eq!(render("ab"), None);
+    }
+
+    #[test]
+    fn report_unknown_glyphs() {
+        let picture = indoc! {"
+            .##..#
+            #..#.#
+            #..#.#
+            ####.#
+            #..#.#
+            #..#.#
+        "};
+        let error = decode(&rows(picture)).unwrap_err();
+        assert_eq!(
+            error,
+            Error::UnknownGlyph {
+                column: 5,
+                glyph: vec!["#".to_owned(); 6]
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "unknown glyph at column 5:\n#\n#\n#\n#\n#\n#"
+        );
+        assert_eq!(decode(&["#"]), Err(Error::Height(1)));
+    }
+}
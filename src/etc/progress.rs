@@ -0,0 +1,260 @@
+use std::collections::BTreeMap;
+use std::fmt::{self, Display, Formatter, Write};
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use crate::etc::answers::{AnswerLog, ParseError};
+use crate::etc::client::YEAR;
+
+/// What is known about solving one day.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DayProgress {
+    /// Unix timestamp of when AoC accepted each part.
+    pub solved_at: [Option<u64>; 2],
+    /// Runtime of the last run of both parts.
+    pub runtime_ms: Option<f64>,
+    /// Whether the last run matched the accepted answer of each part.
+    pub verified: [bool; 2],
+}
+
+impl DayProgress {
+    pub fn is_solved(&self, part: u8, answers: &AnswerLog) -> bool {
+        self.solved_at[part as usize - 1].is_some() || answers.part(part).correct.is_some()
+    }
+}
+
+/// Progress over all days, stored in `answers/progress.txt`.
+///
+/// Every line holds a day, the solve timestamps of both parts, the last
+/// runtime in ms and the verified parts, using `-` for unknown values.
+///
+/// ```text
+/// 1 1669870981 1669871202 0.0412 12
+/// 2 1669957512 - 0.1033 1
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Progress {
+    days: BTreeMap<u8, DayProgress>,
+}
+
+impl Progress {
+    pub fn path() -> PathBuf {
+        PathBuf::from("answers/progress.txt")
+    }
+
+    /// Progress read from [`Progress::path`], or no progress if there is no
+    /// such file.
+    pub fn load() -> Result<Self, ParseError> {
+        let path = Self::path();
+        match fs::read_to_string(&path) {
+            Ok(content) => Self::parse(&content).map_err(|e| ParseError {
+                path: Some(path),
+                ..e
+            }),
+            Err(_) => Ok(Self::default()),
+        }
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let path = Self::path();
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, self.to_string())
+    }
+
+    pub fn parse(content: &str) -> Result<Self, ParseError> {
+        let mut progress = Self::default();
+        for (number, line) in (1..).zip(content.lines().map(str::trim)) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let error = |message: String| ParseError {
+                path: None,
+                line: number,
+                message,
+            };
+            let fields: Vec<_> = line.split_whitespace().collect();
+            let [day, part1, part2, runtime, verified] = fields[..] else {
+                return Err(error(format!(
+                    "expected `<day> <part 1> <part 2> <runtime> <verified>`, got `{line}`"
+                )));
+            };
+            let day: u8 = match day.parse() {
+                Ok(day @ 1..=25) => day,
+                _ => return Err(error(format!("day must be 1 to 25, got `{day}`"))),
+            };
+            progress.days.insert(
+                day,
+                DayProgress {
+                    solved_at: [part1.parse().ok(), part2.parse().ok()],
+                    runtime_ms: runtime.parse().ok(),
+                    verified: [verified.contains('1'), verified.contains('2')],
+                },
+            );
+        }
+        Ok(progress)
+    }
+
+    pub fn day(&self, day: u8) -> DayProgress {
+        self.days.get(&day).cloned().unwrap_or_default()
+    }
+
+    pub fn day_mut(&mut self, day: u8) -> &mut DayProgress {
+        self.days.entry(day).or_default()
+    }
+
+    /// Renders December as a calendar, each day with its stars, whether the last
+    /// run was verified against the accepted answers and its runtime.
+    pub fn calendar(&self, answers: impl Fn(u8) -> AnswerLog) -> String {
+        const WIDTH: usize = 12;
+        let mut out = String::new();
+        let mut stars = 0;
+
+        let header: String = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"]
+            .map(|weekday| format!("{weekday:<WIDTH$}"))
+            .concat();
+        writeln!(out, "{}", header.trim_end()).unwrap();
+
+        let offset = first_weekday_of_december(YEAR);
+        let cells: Vec<Option<u8>> = (0..offset)
+            .map(|_| None)
+            .chain((1..=25).map(Some))
+            .collect();
+        for week in cells.chunks(7) {
+            let (mut top, mut bottom) = (String::new(), String::new());
+            for cell in week {
+                let (head, runtime) = match cell {
+                    None => (String::new(), String::new()),
+                    Some(day) => {
+                        let progress = self.day(*day);
+                        let answers = answers(*day);
+                        let solved: Vec<u8> = (1..=2)
+                            .filter(|&p| progress.is_solved(p, &answers))
+                            .collect();
+                        stars += solved.len();
+                        let mark = match solved.len() {
+                            0 => ' ',
+                            _ if solved.iter().all(|&p| progress.verified[p as usize - 1]) => '✓',
+                            _ => '?',
+                        };
+                        let star = |part| if solved.contains(&part) { '*' } else { '.' };
+                        let runtime = progress
+                            .runtime_ms
+                            .map(|ms| format!("{ms:.2}ms"))
+                            .unwrap_or_default();
+                        (format!("{day:02} {}{} {mark}", star(1), star(2)), runtime)
+                    }
+                };
+                write!(top, "{head:<WIDTH$}").unwrap();
+                write!(bottom, "{runtime:<WIDTH$}").unwrap();
+            }
+            writeln!(out, "{}", top.trim_end()).unwrap();
+            writeln!(out, "{}", bottom.trim_end()).unwrap();
+        }
+
+        writeln!(out, "Stars: {stars}/50").unwrap();
+        out
+    }
+}
+
+/// Weekday of December 1st with Monday as 0, using Sakamoto's method.
+fn first_weekday_of_december(year: u16) -> usize {
+    let y = year as usize;
+    let sunday_based = (y + y / 4 - y / 100 + y / 400 + 4 + 1) % 7;
+    (sunday_based + 6) % 7
+}
+
+fn field<T: Display>(value: Option<T>) -> String {
+    value.map_or_else(|| "-".to_owned(), |v| v.to_string())
+}
+
+impl Display for Progress {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (day, progress) in &self.days {
+            let verified: String = (1..=2)
+                .filter(|&p| progress.verified[p - 1])
+                .map(|p| p.to_string())
+                .collect();
+            writeln!(
+                f,
+                "{day} {} {} {} {}",
+                field(progress.solved_at[0]),
+                field(progress.solved_at[1]),
+                field(progress.runtime_ms.map(|ms| format!("{ms:.4}"))),
+                if verified.is_empty() {
+                    "-".to_owned()
+                } else {
+                    verified
+                },
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::etc::snapshot::assert_snapshot;
+    use indoc::indoc;
+
+    #[test]
+    fn december_2022_starts_on_thursday() {
+        assert_eq!(first_weekday_of_december(2022), 3);
+        assert_eq!(first_weekday_of_december(2023), 4);
+    }
+
+    #[test]
+    fn round_trip() {
+        let content = indoc! {"
+            1 1669870981 1669871202 0.0412 12
+            2 1669957512 - 0.1033 1
+            3 - - - -
+        "};
+        let progress = Progress::parse(content).unwrap();
+        assert_eq!(progress.day(2).solved_at, [Some(1669957512), None]);
+        assert_eq!(progress.day(2).verified, [true, false]);
+        assert_eq!(progress.to_string(), content);
+    }
+
+    #[test]
+    fn report_malformed_lines() {
+        let error = Progress::parse("1 - - - -\n2 - -\n").unwrap_err();
+        assert_eq!(error.line, 2);
+        assert_eq!(
+            error.to_string(),
+            "line 2: expected `<day> <part 1> <part 2> <runtime> <verified>`, got `2 - -`"
+        );
+        let error = Progress::parse("x - - - -").unwrap_err();
+        assert_eq!(error.to_string(), "line 1: day must be 1 to 25, got `x`");
+    }
+
+    #[test]
+    fn calendar_shows_stars() {
+        let mut progress = Progress::default();
+        *progress.day_mut(1) = DayProgress {
+            solved_at: [Some(1), Some(2)],
+            runtime_ms: Some(0.5),
+            verified: [true, true],
+        };
+        progress.day_mut(2).solved_at[0] = Some(3);
+        let calendar = progress.calendar(|day| match day {
+            3 => AnswerLog::parse("1 = 42").unwrap(),
+            _ => AnswerLog::default(),
+        });
+        let lines: Vec<_> = calendar.lines().collect();
+        assert_eq!(
+            lines[1],
+            format!(
+                "{}01 ** ✓     02 *. ?     03 *. ?     04 .. ",
+                " ".repeat(36)
+            )
+            .trim_end()
+        );
+        assert_eq!(lines[2], format!("{}0.50ms", " ".repeat(36)));
+        assert_eq!(lines.last(), Some(&"Stars: 4/50"));
+        assert_snapshot("progress_calendar", &calendar);
+    }
+}
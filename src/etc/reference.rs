@@ -0,0 +1,143 @@
+use std::env;
+use std::fmt::{self, Display, Formatter};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+use crate::SolutionPair;
+
+/// Independent reference solution of a day, kept as `src/days/dayNN.py`.
+///
+/// Scripts are run as `<python> src/days/dayNN.py -` with the puzzle input on
+/// stdin and print the answers of both parts on one line each. The interpreter
+/// is `python3`, or the one named by `PYTHON`.
+#[derive(Clone, Debug)]
+pub struct Reference {
+    pub day: u8,
+    pub script: PathBuf,
+}
+
+/// Part on which the Rust solver and the reference script disagree.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Disagreement {
+    pub part: u8,
+    pub rust: String,
+    pub reference: String,
+}
+
+impl Display for Disagreement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "part {}: Rust answered {}, the reference {}",
+            self.part, self.rust, self.reference
+        )
+    }
+}
+
+impl Reference {
+    /// Reference script of `day`, if there is one.
+    pub fn find(day: u8) -> Option<Self> {
+        let script = PathBuf::from(format!("src/days/day{day:02}.py"));
+        script.exists().then_some(Self { day, script })
+    }
+
+    /// Whether the interpreter for the scripts can be started at all.
+    pub fn python_available() -> bool {
+        Command::new(python())
+            .arg("--version")
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .is_ok_and(|status| status.success())
+    }
+
+    /// Answers of the script for both parts on `input`.
+    pub fn solve(&self, input: &str) -> io::Result<[String; 2]> {
+        let mut child = Command::new(python())
+            .arg(&self.script)
+            .arg("-")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        child
+            .stdin
+            .take()
+            .expect("stdin is piped")
+            .write_all(input.as_bytes())?;
+        let output = child.wait_with_output()?;
+        if !output.status.success() {
+            return Err(io::Error::other(format!(
+                "{} failed: {}",
+                self.script.display(),
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let mut lines = stdout.lines().map(|line| line.trim().to_owned());
+        match (lines.next(), lines.next()) {
+            (Some(p1), Some(p2)) => Ok([p1, p2]),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{} did not print two answers", self.script.display()),
+            )),
+        }
+    }
+
+    /// Runs `solver` and the script on `input`, returning the parts they disagree on.
+    pub fn compare(
+        &self,
+        solver: fn(&str) -> SolutionPair,
+        input: &str,
+    ) -> io::Result<Vec<Disagreement>> {
+        let reference = self.solve(input)?;
+        let (p1, p2) = solver(input);
+        Ok([p1.to_string(), p2.to_string()]
+            .into_iter()
+            .zip(reference)
+            .zip(1..)
+            .filter(|((rust, reference), _)| rust != reference)
+            .map(|((rust, reference), part)| Disagreement {
+                part,
+                rust,
+                reference,
+            })
+            .collect())
+    }
+}
+
+fn python() -> String {
+    env::var("PYTHON").unwrap_or_else(|_| "python3".to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::day13;
+
+    #[test]
+    fn find_scripts() {
+        assert!(Reference::find(13).is_some());
+        assert!(Reference::find(4).is_none());
+    }
+
+    #[test]
+    fn agree_on_example() {
+        if !Reference::python_available() {
+            return;
+        }
+        let reference = Reference::find(13).unwrap();
+        let input = "[1,[2]]\n[[1],3]\n\n[]\n[[]]\n";
+        assert_eq!(reference.compare(day13::solve, input).unwrap(), vec![]);
+        assert_eq!(
+            reference.compare(|_| (0.into(), 0.into()), input).unwrap()[0],
+            Disagreement {
+                part: 1,
+                rust: "0".to_owned(),
+                reference: "3".to_owned(),
+            }
+        );
+    }
+}
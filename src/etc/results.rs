@@ -0,0 +1,109 @@
+//! Records of what running a day came to, for history files and JSON output.
+//!
+//! With the `serde` feature the records implement `Serialize` and
+//! `Deserialize`, and the runner prints one as a JSON line per day on `--json`.
+
+use crate::Solution;
+
+/// Answer and timing of one part.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PartResult {
+    pub part: u8,
+    pub variant: String,
+    pub answer: Solution,
+    pub elapsed_ms: f64,
+    /// Whether the answer matches the one AoC accepted.
+    pub verified: bool,
+}
+
+/// Results of both parts of a day.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DayResult {
+    pub day: u8,
+    /// Whether the parts ran on the example instead of the puzzle input.
+    pub example: bool,
+    pub parts: Vec<PartResult>,
+}
+
+impl DayResult {
+    /// Time spent on the parts that came to an answer, leaving out unsolved
+    /// parts and errors.
+    pub fn elapsed_ms(&self) -> f64 {
+        self.parts
+            .iter()
+            .filter(|part| part.answer.is_solved())
+            .fold(0.0, |total, part| total + part.elapsed_ms)
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+
+    fn round_trip(solution: Solution) -> String {
+        let json = serde_json::to_string(&solution).unwrap();
+        let back: Solution = serde_json::from_str(&json).unwrap();
+        assert_eq!(format!("{back:?}"), format!("{solution:?}"));
+        json
+    }
+
+    #[test]
+    fn solutions_keep_their_variant() {
+        assert_eq!(
+            round_trip(Solution::U128(u128::MAX)),
+            "{\"type\":\"U128\",\"value\":\"340282366920938463463374607431768211455\"}"
+        );
+        assert_eq!(
+            round_trip(Solution::I8(-3)),
+            "{\"type\":\"I8\",\"value\":-3}"
+        );
+        assert_eq!(
+            round_trip(Solution::I128(i128::MIN)),
+            "{\"type\":\"I128\",\"value\":\"-170141183460469231731687303715884105728\"}"
+        );
+        let big = Solution::BigInt("-1000000000000000000000000000000000000000".parse().unwrap());
+        assert_eq!(
+            round_trip(big),
+            "{\"type\":\"BigInt\",\"value\":\"-1000000000000000000000000000000000000000\"}"
+        );
+        assert_eq!(
+            round_trip(Solution::Str("\n#..#\n#..#".to_owned())),
+            "{\"type\":\"Str\",\"value\":\"\\n#..#\\n#..#\"}"
+        );
+        assert_eq!(
+            round_trip(Solution::from(vec!["#..#".to_owned(), "#..#".to_owned()])),
+            "{\"type\":\"Grid\",\"value\":[\"#..#\",\"#..#\"]}"
+        );
+    }
+
+    #[test]
+    fn day_results_round_trip() {
+        let result = DayResult {
+            day: 11,
+            example: false,
+            parts: vec![
+                PartResult {
+                    part: 1,
+                    variant: "default".to_owned(),
+                    answer: Solution::U64(10605),
+                    elapsed_ms: 0.25,
+                    verified: true,
+                },
+                PartResult {
+                    part: 2,
+                    variant: "default".to_owned(),
+                    answer: Solution::U128(2713310158),
+                    elapsed_ms: 1.5,
+                    verified: false,
+                },
+            ],
+        };
+        let json = serde_json::to_string(&result).unwrap();
+        let back: DayResult = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, result);
+        assert!(matches!(back.parts[1].answer, Solution::U128(2713310158)));
+        assert_eq!(back.elapsed_ms(), 1.75);
+    }
+}
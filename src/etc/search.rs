@@ -0,0 +1,308 @@
+//! Shortest paths through graphs given by closures, with breadth-first search,
+//! Dijkstra and A*.
+//!
+//! All searches start from any number of nodes at once and stop at the first
+//! node `goal` accepts. A goal that accepts nothing explores everything
+//! reachable, which turns the result into a distance map.
+
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// What a search found: the goal it stopped at, if any, and the distance and
+/// predecessor of each node it reached.
+#[derive(Clone, Debug)]
+pub struct Search<N, C> {
+    goal: Option<N>,
+    distances: HashMap<N, C>,
+    predecessors: HashMap<N, N>,
+}
+
+impl<N: Eq + Hash + Clone, C: Copy> Search<N, C> {
+    pub fn goal(&self) -> Option<&N> {
+        self.goal.as_ref()
+    }
+
+    /// Distance of the goal from the nearest start.
+    pub fn cost(&self) -> Option<C> {
+        self.distance(self.goal.as_ref()?)
+    }
+
+    /// Shortest path from a start to the goal, both included.
+    pub fn path(&self) -> Option<Vec<N>> {
+        self.path_to(self.goal.as_ref()?)
+    }
+
+    /// Distance of `node` from the nearest start, or `None` if it was not
+    /// reached.
+    ///
+    /// Weighted searches that stopped at a goal may not have settled the nodes
+    /// they had yet to visit, for these it is only an upper bound.
+    pub fn distance(&self, node: &N) -> Option<C> {
+        self.distances.get(node).copied()
+    }
+
+    /// Distances of all nodes reached.
+    pub fn distances(&self) -> &HashMap<N, C> {
+        &self.distances
+    }
+
+    /// Path from a start to `node` along the predecessors, both included.
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        self.distances.get(node)?;
+        let mut path = vec![node.clone()];
+        while let Some(previous) = self.predecessors.get(path.last().unwrap()) {
+            path.push(previous.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+/// Breadth-first search, where every step costs one.
+pub fn bfs<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut goal: impl FnMut(&N) -> bool,
+) -> Search<N, usize>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut search = Search {
+        goal: None,
+        distances: HashMap::new(),
+        predecessors: HashMap::new(),
+    };
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if !search.distances.contains_key(&start) {
+            search.distances.insert(start.clone(), 0);
+            queue.push_back((start, 0));
+        }
+    }
+
+    while let Some((node, distance)) = queue.pop_front() {
+        if goal(&node) {
+            search.goal = Some(node);
+            break;
+        }
+        for next in neighbours(&node) {
+            if !search.distances.contains_key(&next) {
+                search.distances.insert(next.clone(), distance + 1);
+                search.predecessors.insert(next.clone(), node.clone());
+                queue.push_back((next, distance + 1));
+            }
+        }
+    }
+    search
+}
+
+/// Dijkstra's search, with `neighbours` giving the cost of the step to each
+/// neighbour. Costs must not be negative.
+pub fn dijkstra<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    neighbours: impl FnMut(&N) -> I,
+    goal: impl FnMut(&N) -> bool,
+) -> Search<N, C>
+where
+    N: Eq + Hash + Clone,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(starts, neighbours, |_| C::default(), goal)
+}
+
+/// A* search, which visits nodes in order of their distance plus `heuristic`'s
+/// estimate of the cost left to the goal. The estimate must never be more than
+/// the actual cost, or the path found may not be the shortest.
+pub fn astar<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut goal: impl FnMut(&N) -> bool,
+) -> Search<N, C>
+where
+    N: Eq + Hash + Clone,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut search = Search {
+        goal: None,
+        distances: HashMap::new(),
+        predecessors: HashMap::new(),
+    };
+    let mut frontier = BinaryHeap::new();
+    for start in starts {
+        search.distances.insert(start.clone(), C::default());
+        frontier.push(Frontier {
+            estimate: heuristic(&start),
+            distance: C::default(),
+            node: start,
+        });
+    }
+
+    while let Some(Frontier { distance, node, .. }) = frontier.pop() {
+        // left over from before a shorter way to the node was found
+        if search.distances[&node] < distance {
+            continue;
+        }
+        if goal(&node) {
+            search.goal = Some(node);
+            break;
+        }
+        for (next, cost) in neighbours(&node) {
+            let next_distance = distance + cost;
+            if search
+                .distances
+                .get(&next)
+                .is_some_and(|&known| known <= next_distance)
+            {
+                continue;
+            }
+            search.distances.insert(next.clone(), next_distance);
+            search.predecessors.insert(next.clone(), node.clone());
+            frontier.push(Frontier {
+                estimate: next_distance + heuristic(&next),
+                distance: next_distance,
+                node: next,
+            });
+        }
+    }
+    search
+}
+
+/// Node waiting to be visited, ordered so that the max-heap `BinaryHeap` pops
+/// the lowest estimate first.
+struct Frontier<N, C> {
+    estimate: C,
+    distance: C,
+    node: N,
+}
+
+impl<N, C: Ord> PartialEq for Frontier<N, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.estimate == other.estimate
+    }
+}
+
+impl<N, C: Ord> Eq for Frontier<N, C> {}
+
+impl<N, C: Ord> PartialOrd for Frontier<N, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N, C: Ord> Ord for Frontier<N, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.estimate.cmp(&self.estimate)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::etc::grid::{Grid, Point};
+    use indoc::indoc;
+    use proptest::prelude::*;
+
+    fn maze() -> Grid<bool> {
+        let input = indoc! {"
+            S..#....
+            .#.#.##.
+            .#...#..
+            .####.#.
+            ......#E
+        "};
+        Grid::parse(input, |c| c != '#')
+    }
+
+    fn open_neighbours(maze: &Grid<bool>, point: Point) -> impl Iterator<Item = Point> + '_ {
+        maze.neighbours4(point)
+            .filter(|(_, &open)| open)
+            .map(|(point, _)| point)
+    }
+
+    const START: Point = Point::new(0, 0);
+    const END: Point = Point::new(7, 4);
+
+    #[test]
+    fn bfs_finds_the_shortest_path() {
+        let maze = maze();
+        let search = bfs([START], |&p| open_neighbours(&maze, p), |&p| p == END);
+        assert_eq!(search.cost(), Some(15));
+        let path = search.path().unwrap();
+        assert_eq!((path[0], path[15]), (START, END));
+        assert!(path.windows(2).all(|step| step[0].manhattan(step[1]) == 1));
+        assert_eq!(search.goal(), Some(&END));
+    }
+
+    #[test]
+    fn distance_maps_and_several_starts() {
+        let maze = maze();
+        let everything = bfs([START], |&p| open_neighbours(&maze, p), |_| false);
+        assert_eq!(everything.goal(), None);
+        assert_eq!(
+            everything.distances().len(),
+            maze.values().filter(|&&open| open).count()
+        );
+        assert_eq!(everything.distance(&Point::new(4, 0)), Some(8));
+        assert_eq!(everything.distance(&Point::new(3, 0)), None);
+
+        let starts = [START, Point::new(7, 0)];
+        let search = bfs(starts, |&p| open_neighbours(&maze, p), |&p| p == END);
+        assert_eq!(search.cost(), Some(4));
+        assert_eq!(search.path().unwrap()[0], Point::new(7, 0));
+        assert_eq!(search.path_to(&START), Some(vec![START]));
+    }
+
+    #[test]
+    fn dijkstra_weighs_steps() {
+        // going round a costly node in the middle of a line is cheaper
+        let cost = |node: u32| if node == 2 { 10 } else { 1 };
+        let neighbours = |&node: &u32| {
+            let mut next = vec![(node + 1, cost(node + 1)), (node + 10, 3)];
+            if node >= 10 {
+                next.push((node - 10, 3));
+            }
+            next.into_iter().filter(|&(next, _)| next < 20)
+        };
+        let search = dijkstra([1], neighbours, |&node| node == 3);
+        assert_eq!(search.cost(), Some(8));
+        assert_eq!(search.path(), Some(vec![1, 11, 12, 13, 3]));
+        let search = dijkstra([1], neighbours, |&node| node == 42);
+        assert_eq!((search.goal(), search.cost()), (None, None));
+    }
+
+    fn open_maze() -> impl Strategy<Value = Grid<bool>> {
+        prop::collection::vec(prop::bool::weighted(0.7), 64).prop_map(|cells| {
+            let input: Vec<String> = cells
+                .chunks(8)
+                .map(|row| {
+                    row.iter()
+                        .map(|&open| if open { '.' } else { '#' })
+                        .collect()
+                })
+                .collect();
+            Grid::parse(&input.join("\n"), |c| c == '.')
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn all_searches_agree(maze in open_maze()) {
+            let end = Point::new(7, 7);
+            let steps = bfs([START], |&p| open_neighbours(&maze, p), |&p| p == end).cost();
+            let weighted = |&p: &Point| open_neighbours(&maze, p).map(|next| (next, 1));
+            let shortest = dijkstra([START], weighted, |&p| p == end);
+            prop_assert_eq!(shortest.cost(), steps);
+            let guided = astar([START], weighted, |p| p.manhattan(end) as usize, |&p| p == end);
+            prop_assert_eq!(guided.cost(), steps);
+            if let Some(path) = guided.path() {
+                prop_assert_eq!(path.len(), steps.unwrap() + 1);
+            }
+        }
+    }
+}
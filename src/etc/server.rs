@@ -0,0 +1,337 @@
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::panic;
+use std::time::{Duration, Instant};
+
+use crate::etc::json;
+use crate::{Day, Solution};
+
+pub type Registry = fn(u8) -> Option<Day>;
+
+/// Largest request body accepted, well above any puzzle input.
+const MAX_BODY: usize = 1 << 20;
+
+/// How long a client may take to send its whole request.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Serves the solvers of `registry` over HTTP, one request at a time.
+///
+/// - `GET /days` lists the available days.
+/// - `POST /days/<day>/<part>` solves the request body as input and answers
+///   with `{"day", "part", "answer", "elapsed_ms", "error"}`. Bodies over
+///   `MAX_BODY` bytes are refused with status 413.
+///
+/// Clients that have not sent their whole request within `REQUEST_TIMEOUT`
+/// get status 408 and are dropped.
+pub fn serve(listener: TcpListener, registry: Registry) -> io::Result<()> {
+    serve_within(listener, registry, REQUEST_TIMEOUT)
+}
+
+fn serve_within(listener: TcpListener, registry: Registry, timeout: Duration) -> io::Result<()> {
+    for stream in listener.incoming() {
+        let mut stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                eprintln!("(!) Could not accept a connection: {e}");
+                continue;
+            }
+        };
+        let deadline = Instant::now() + timeout;
+        let (status, body) = match read_request(&stream, deadline) {
+            Ok((method, path, input)) => route(&method, &path, &input, registry),
+            Err((status, message)) => (status, error_json(&message)),
+        };
+        let _ = write!(
+            stream,
+            "HTTP/1.1 {status} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            reason(status),
+            body.len()
+        );
+    }
+    Ok(())
+}
+
+/// Method, path and body of the request on `stream`, or the status and message
+/// to refuse it with.
+fn read_request(
+    stream: &TcpStream,
+    deadline: Instant,
+) -> Result<(String, String, String), (u16, String)> {
+    let bad_request = |e: io::Error| match e.kind() {
+        io::ErrorKind::TimedOut | io::ErrorKind::WouldBlock => {
+            (408, "request not sent in time".to_owned())
+        }
+        _ => (400, e.to_string()),
+    };
+    // lines are limited as well, so that a client can't send one endless header
+    let mut reader = BufReader::new(Deadline { stream, deadline }).take(MAX_BODY as u64);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).map_err(bad_request)?;
+    let mut parts = request_line.split_whitespace();
+    let (method, path) = match (parts.next(), parts.next()) {
+        (Some(method), Some(path)) => (method.to_owned(), path.to_owned()),
+        _ => return Err((400, "malformed request line".to_owned())),
+    };
+
+    let mut content_length = 0;
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line).map_err(bad_request)?;
+        if let Some((name, value)) = line.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value
+                    .trim()
+                    .parse()
+                    .map_err(|_| (400, "invalid content-length".to_owned()))?;
+            }
+        }
+        if line.trim_end().is_empty() {
+            break;
+        }
+    }
+    if content_length > MAX_BODY {
+        return Err((413, format!("input is longer than {MAX_BODY} bytes")));
+    }
+
+    let mut body = vec![0; content_length];
+    reader.set_limit(content_length as u64);
+    reader.read_exact(&mut body).map_err(bad_request)?;
+    let body = String::from_utf8(body).map_err(|_| (400, "input is not UTF-8".to_owned()))?;
+    Ok((method, path, body))
+}
+
+/// Reader of a request that fails once `deadline` has passed, however slowly
+/// the client keeps sending it.
+struct Deadline<'a> {
+    stream: &'a TcpStream,
+    deadline: Instant,
+}
+
+impl Read for Deadline<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let left = self.deadline.saturating_duration_since(Instant::now());
+        if left.is_zero() {
+            return Err(io::ErrorKind::TimedOut.into());
+        }
+        let mut stream = self.stream;
+        stream.set_read_timeout(Some(left))?;
+        stream.read(buf)
+    }
+}
+
+fn route(method: &str, path: &str, input: &str, registry: Registry) -> (u16, String) {
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+    match (method, segments.as_slice()) {
+        ("GET", ["days"]) => {
+            let days: Vec<String> = (1..=25)
+                .filter(|&day| registry(day).is_some())
+                .map(|day| day.to_string())
+                .collect();
+            (200, format!("{{\"days\":[{}]}}", days.join(",")))
+        }
+        ("POST", ["days", day, part]) => {
+            let day: u8 = match day.parse() {
+                Ok(day) => day,
+                Err(_) => return (400, error_json(&format!("not a valid day: {day}"))),
+            };
+            let part: u8 = match part.parse() {
+                Ok(part @ 1..=2) => part,
+                _ => return (400, error_json(&format!("not a valid part: {part}"))),
+            };
+            match registry(day) {
+                Some(entry) => {
+                    let solver = if part == 1 { entry.part1 } else { entry.part2 };
+                    solve(day, part, solver, input)
+                }
+                None => (404, error_json(&format!("day {day} is not implemented"))),
+            }
+        }
+        (_, ["days", ..]) => (405, error_json("method not allowed")),
+        _ => (404, error_json("not found")),
+    }
+}
+
+/// Runs `solver`, the entry of a single part, on `input`.
+fn solve(day: u8, part: u8, solver: fn(&str) -> Solution, input: &str) -> (u16, String) {
+    let time = Instant::now();
+    let result = panic::catch_unwind(|| solver(input));
+    let elapsed_ms = time.elapsed().as_nanos() as f64 / 1_000_000.0;
+
+    let (status, answer, error) = match result {
+        Ok(solution) => match solution {
+            Solution::Unsolved => (501, "null".to_owned(), json::string("unsolved")),
+            Solution::Error(message) => (422, "null".to_owned(), json::string(&message)),
+            solution => (200, answer_json(&solution), "null".to_owned()),
+        },
+        Err(panic) => {
+            let message = panic
+                .downcast_ref::<String>()
+                .map(String::as_str)
+                .or_else(|| panic.downcast_ref::<&str>().copied())
+                .unwrap_or("solver panicked");
+            (422, "null".to_owned(), json::string(message))
+        }
+    };
+    let body = format!(
+        "{{\"day\":{day},\"part\":{part},\"answer\":{answer},\"elapsed_ms\":{elapsed_ms:.4},\"error\":{error}}}"
+    );
+    (status, body)
+}
+
+/// `solution` as JSON, a grid as the array of its rows.
+fn answer_json(solution: &Solution) -> String {
+    match solution {
+        Solution::Grid(rows) => {
+            let rows: Vec<String> = rows.iter().map(|row| json::string(row)).collect();
+            format!("[{}]", rows.join(","))
+        }
+        solution => json::string(&solution.to_string()),
+    }
+}
+
+fn error_json(message: &str) -> String {
+    format!("{{\"error\":{}}}", json::string(message))
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        408 => "Request Timeout",
+        413 => "Payload Too Large",
+        422 => "Unprocessable Entity",
+        501 => "Not Implemented",
+        _ => "Error",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::get_day;
+    use std::thread;
+
+    fn start() -> String {
+        start_with(get_day)
+    }
+
+    fn start_with(registry: Registry) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        thread::spawn(move || serve(listener, registry));
+        url
+    }
+
+    fn call(request: ureq::Request, body: &str) -> (u16, String) {
+        match request.send_string(body) {
+            Ok(response) => (response.status(), response.into_string().unwrap()),
+            Err(ureq::Error::Status(status, response)) => (status, response.into_string().unwrap()),
+            Err(e) => panic!("{e}"),
+        }
+    }
+
+    #[test]
+    fn list_days() {
+        let url = start();
+        let (status, body) = call(ureq::get(&format!("{url}/days")), "");
+        assert_eq!(status, 200);
+        assert!(body.starts_with("{\"days\":[1,2,3,"));
+    }
+
+    #[test]
+    fn solve_part() {
+        let url = start();
+        let (status, body) = call(ureq::post(&format!("{url}/days/4/2")), "2-4,6-8\n2-6,4-8\n");
+        assert_eq!(status, 200);
+        assert!(body.starts_with("{\"day\":4,\"part\":2,\"answer\":\"1\",\"elapsed_ms\":"));
+        assert!(body.ends_with(",\"error\":null}"));
+    }
+
+    #[test]
+    fn report_errors() {
+        let url = start();
+        let (status, body) = call(ureq::post(&format!("{url}/days/4/1")), "nonsense\n");
+        assert_eq!(status, 422);
+        assert!(body.contains("\"answer\":null"));
+        assert!(!body.contains("\"error\":null"));
+
+        let (status, body) = call(ureq::post(&format!("{url}/days/12/1")), "SbcE\n");
+        assert_eq!(status, 422);
+        assert!(body.contains("\"error\":\"no path to the best signal\""));
+        let (status, _) = call(ureq::post(&format!("{url}/days/25/1")), "");
+        assert_eq!(status, 501);
+
+        let (status, _) = call(ureq::post(&format!("{url}/days/26/1")), "");
+        assert_eq!(status, 404);
+        let (status, _) = call(ureq::post(&format!("{url}/days/4/3")), "");
+        assert_eq!(status, 400);
+    }
+
+    #[test]
+    fn solve_only_the_requested_part() {
+        let url = start_with(|_| {
+            Some(Day {
+                solve: |_| panic!("both parts were run"),
+                parse: None,
+                part1: |input| Solution::from(input.len()),
+                part2: |_| panic!("part 2 was run"),
+                variants: &[],
+                explain: None,
+            })
+        });
+        let (status, body) = call(ureq::post(&format!("{url}/days/1/1")), "abc");
+        assert_eq!(status, 200);
+        assert!(body.contains("\"answer\":\"3\""));
+        let (status, body) = call(ureq::post(&format!("{url}/days/1/2")), "abc");
+        assert_eq!(status, 422);
+        assert!(body.contains("\"error\":\"part 2 was run\""));
+    }
+
+    #[test]
+    fn refuse_large_bodies() {
+        let url = start();
+        let mut stream = TcpStream::connect(url.trim_start_matches("http://")).unwrap();
+        write!(
+            stream,
+            "POST /days/1/1 HTTP/1.1\r\nContent-Length: 99999999999\r\n\r\n"
+        )
+        .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 413 Payload Too Large\r\n"));
+    }
+
+    #[test]
+    fn drop_clients_that_trickle() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        thread::spawn(move || serve_within(listener, get_day, Duration::from_millis(500)));
+
+        let mut stream = TcpStream::connect(addr).unwrap();
+        stream
+            .set_read_timeout(Some(Duration::from_secs(10)))
+            .unwrap();
+        let mut writer = stream.try_clone().unwrap();
+        // a byte of an endless request line every 100 ms, each well in time
+        thread::spawn(move || {
+            while writer.write_all(b"x").is_ok() {
+                thread::sleep(Duration::from_millis(100));
+            }
+        });
+        let time = Instant::now();
+        let _ = stream.read_to_end(&mut Vec::new());
+        assert!(time.elapsed() < Duration::from_secs(5));
+
+        // and the server is free for the next client
+        let (status, _) = call(ureq::get(&format!("http://{addr}/days")), "");
+        assert_eq!(status, 200);
+    }
+
+    #[test]
+    fn grids_as_arrays() {
+        let grid = Solution::from(vec!["#.".to_owned(), ".#".to_owned()]);
+        assert_eq!(answer_json(&grid), "[\"#.\",\".#\"]");
+    }
+}
@@ -0,0 +1,94 @@
+//! Golden snapshots of multi-line outputs such as rendered grids, kept in
+//! `tests/snapshots/<name>.txt`.
+//!
+//! Running the tests with `UPDATE_SNAPSHOTS=1` writes the actual outputs to the
+//! snapshot files instead of comparing against them, so that changes to a
+//! rendering show up as a diff of those files.
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+pub const UPDATE_VAR: &str = "UPDATE_SNAPSHOTS";
+
+pub fn path(name: &str) -> PathBuf {
+    PathBuf::from(format!("tests/snapshots/{name}.txt"))
+}
+
+/// Asserts that `actual` matches the snapshot `name`, or updates the snapshot
+/// when `UPDATE_SNAPSHOTS` is set.
+#[track_caller]
+pub fn assert_snapshot(name: &str, actual: &str) {
+    let path = path(name);
+    if env::var_os(UPDATE_VAR).is_some_and(|value| !value.is_empty() && value != "0") {
+        fs::create_dir_all("tests/snapshots").unwrap();
+        fs::write(&path, actual)
+            .unwrap_or_else(|e| panic!("could not write {}: {e}", path.display()));
+        return;
+    }
+
+    let expected = match fs::read_to_string(&path) {
+        Ok(expected) => expected,
+        Err(_) => panic!(
+            "no snapshot {}, run with {UPDATE_VAR}=1 to create it from:\n{actual}",
+            path.display()
+        ),
+    };
+    if expected != actual {
+        panic!(
+            "snapshot {} does not match (- expected, + actual), run with {UPDATE_VAR}=1 to update it:\n{}",
+            path.display(),
+            diff(&expected, actual)
+        );
+    }
+}
+
+/// Line diff of `expected` and `actual`, marking lines only in `expected` with
+/// `-`, lines only in `actual` with `+` and common lines with a space.
+pub fn diff(expected: &str, actual: &str) -> String {
+    let expected: Vec<&str> = expected.lines().collect();
+    let actual: Vec<&str> = actual.lines().collect();
+
+    // longest common subsequence of the suffixes starting at i and j
+    let mut common = vec![vec![0; actual.len() + 1]; expected.len() + 1];
+    for i in (0..expected.len()).rev() {
+        for j in (0..actual.len()).rev() {
+            common[i][j] = if expected[i] == actual[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    let mut out = String::new();
+    let (mut i, mut j) = (0, 0);
+    while i < expected.len() || j < actual.len() {
+        if i < expected.len() && j < actual.len() && expected[i] == actual[j] {
+            out += &format!("  {}\n", expected[i]);
+            (i, j) = (i + 1, j + 1);
+        } else if j == actual.len() || (i < expected.len() && common[i + 1][j] >= common[i][j + 1])
+        {
+            out += &format!("- {}\n", expected[i]);
+            i += 1;
+        } else {
+            out += &format!("+ {}\n", actual[j]);
+            j += 1;
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff_lines() {
+        assert_eq!(
+            diff("a\nb\nc\n", "a\nx\nc\nd\n"),
+            "  a\n- b\n+ x\n  c\n+ d\n"
+        );
+        assert_eq!(diff("same\n", "same\n"), "  same\n");
+    }
+}
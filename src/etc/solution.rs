@@ -1,21 +1,74 @@
+use crate::etc::bigint::{self, BigInt};
+use std::convert::Infallible;
 use std::fmt::{Display, Formatter, Result};
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
 use Solution::*;
 
-#[derive(Clone)]
+/// Answer of a part.
+///
+/// Integer answers compare and hash by value whatever their width, so that
+/// `U64(5) == I32(5)`, while `Str` and `Grid` answers only equal the same
+/// `Str` or `Grid`.
+///
+/// With the `serde` feature, answers serialize tagged with their variant, as
+/// `{"type":"U64","value":10605}`, so that they read back exactly as they were.
+/// 128-bit integers serialize as strings, like `BigInt`, since JSON readers
+/// often turn large numbers into floats.
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", content = "value")
+)]
 pub enum Solution {
     I8(i8),
     I16(i16),
     I32(i32),
     I64(i64),
+    #[cfg_attr(feature = "serde", serde(with = "as_string"))]
     I128(i128),
     Isize(isize),
     U8(u8),
     U16(u16),
     U32(u32),
     U64(u64),
+    #[cfg_attr(feature = "serde", serde(with = "as_string"))]
     U128(u128),
     Usize(usize),
+    /// Integer beyond the range of `u128` and `i128`, serialized as a string.
+    BigInt(BigInt),
     Str(String),
+    /// Picture drawn by the puzzle, as its rows of cells.
+    Grid(Vec<String>),
+    /// The part has no solver yet.
+    Unsolved,
+    /// The solver found no answer, for the given reason.
+    Error(String),
+}
+
+/// Serde helpers for the variants that serialize as decimal strings.
+#[cfg(feature = "serde")]
+mod as_string {
+    use std::fmt::Display;
+    use std::str::FromStr;
+
+    pub fn serialize<T: Display, S: serde::Serializer>(
+        value: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(value)
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: FromStr,
+        T::Err: Display,
+        D: serde::Deserializer<'de>,
+    {
+        let s = <String as serde::Deserialize>::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
 }
 
 impl Display for Solution {
@@ -33,7 +86,11 @@ impl Display for Solution {
             U64(x) => x.fmt(f),
             U128(x) => x.fmt(f),
             Usize(x) => x.fmt(f),
+            BigInt(x) => x.fmt(f),
             Str(x) => x.fmt(f),
+            Grid(rows) => write!(f, "{}", rows.join("\n")),
+            Unsolved => write!(f, "unsolved"),
+            Error(message) => write!(f, "error: {message}"),
         }
     }
 }
@@ -45,7 +102,7 @@ macro_rules! impl_from {
                 Self::$kind_(sol)
             }
         }
-    }
+    };
 }
 
 impl_from!(i8, I8);
@@ -60,10 +117,234 @@ impl_from!(u32, U32);
 impl_from!(u64, U64);
 impl_from!(u128, U128);
 impl_from!(usize, Usize);
+impl_from!(BigInt, BigInt);
 impl_from!(String, Str);
 
+/// The answer, or an `Error` saying why there is none.
+impl<T: Into<Solution>, E: Display> From<std::result::Result<T, E>> for Solution {
+    fn from(result: std::result::Result<T, E>) -> Self {
+        match result {
+            Ok(answer) => answer.into(),
+            Err(error) => Error(error.to_string()),
+        }
+    }
+}
+
+impl From<bigint::Int> for Solution {
+    fn from(x: bigint::Int) -> Self {
+        match x {
+            bigint::Int::Small(x) => I128(x),
+            bigint::Int::Big(x) => BigInt(x),
+        }
+    }
+}
+
 impl From<&str> for Solution {
     fn from(sol: &str) -> Self {
         Self::Str(sol.to_owned())
     }
 }
+
+impl From<Vec<String>> for Solution {
+    fn from(rows: Vec<String>) -> Self {
+        Self::Grid(rows)
+    }
+}
+
+/// Grid of lit cells, drawn as `#` and `.`.
+impl From<Vec<Vec<bool>>> for Solution {
+    fn from(cells: Vec<Vec<bool>>) -> Self {
+        Self::Grid(
+            cells
+                .iter()
+                .map(|row| row.iter().map(|&lit| if lit { '#' } else { '.' }).collect())
+                .collect(),
+        )
+    }
+}
+
+/// Value of an integer answer as sign and magnitude, with zero never negative.
+#[derive(PartialEq, Eq, Hash)]
+enum Integer {
+    Negative(u128),
+    NonNegative(u128),
+}
+
+impl Integer {
+    fn signed(value: i128) -> Self {
+        if value < 0 {
+            Integer::Negative(value.unsigned_abs())
+        } else {
+            Integer::NonNegative(value as u128)
+        }
+    }
+}
+
+impl Solution {
+    /// Whether this is an answer, rather than `Unsolved` or an `Error`.
+    pub fn is_solved(&self) -> bool {
+        !matches!(self, Unsolved | Error(_))
+    }
+
+    fn integer(&self) -> Option<Integer> {
+        Some(match *self {
+            I8(x) => Integer::signed(x.into()),
+            I16(x) => Integer::signed(x.into()),
+            I32(x) => Integer::signed(x.into()),
+            I64(x) => Integer::signed(x.into()),
+            I128(x) => Integer::signed(x),
+            Isize(x) => Integer::signed(x as i128),
+            U8(x) => Integer::NonNegative(x.into()),
+            U16(x) => Integer::NonNegative(x.into()),
+            U32(x) => Integer::NonNegative(x.into()),
+            U64(x) => Integer::NonNegative(x.into()),
+            U128(x) => Integer::NonNegative(x),
+            Usize(x) => Integer::NonNegative(x as u128),
+            BigInt(ref x) => match x.magnitude_u128()? {
+                magnitude if x.is_negative() => Integer::Negative(magnitude),
+                magnitude => Integer::NonNegative(magnitude),
+            },
+            Str(_) | Grid(_) | Unsolved | Error(_) => return None,
+        })
+    }
+}
+
+impl PartialEq for Solution {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Str(a), Str(b)) => a == b,
+            (Grid(a), Grid(b)) => a == b,
+            (Unsolved, Unsolved) => true,
+            (Error(a), Error(b)) => a == b,
+            (BigInt(a), BigInt(b)) => a == b,
+            // a big integer beyond 128 bits only equals another big integer
+            _ => self.integer().is_some_and(|a| other.integer() == Some(a)),
+        }
+    }
+}
+
+impl Eq for Solution {}
+
+impl Hash for Solution {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match self {
+            Str(x) => x.hash(state),
+            Grid(rows) => rows.hash(state),
+            Unsolved => {}
+            Error(message) => message.hash(state),
+            BigInt(x) if x.magnitude_u128().is_none() => x.hash(state),
+            _ => self.integer().hash(state),
+        }
+    }
+}
+
+/// Reads an answer back from its text: integers as the narrowest of `U64`,
+/// `I64`, `U128`, `I128` and `BigInt` they fit in, anything else as `Str`.
+impl FromStr for Solution {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let solution = if let Ok(x) = s.parse::<u64>() {
+            U64(x)
+        } else if let Ok(x) = s.parse::<i64>() {
+            I64(x)
+        } else if let Ok(x) = s.parse::<u128>() {
+            U128(x)
+        } else if let Ok(x) = s.parse::<i128>() {
+            I128(x)
+        } else if let Ok(x) = s.parse::<BigInt>() {
+            BigInt(x)
+        } else {
+            Str(s.to_owned())
+        };
+        Ok(solution)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn integers_compare_by_value() {
+        assert_eq!(U64(5), I32(5));
+        assert_eq!(I8(-3), I128(-3));
+        assert_eq!(U128(u128::MAX), U128(u128::MAX));
+        assert_ne!(I64(-5), U64(5));
+        assert_ne!(U64(5), Str("5".to_owned()));
+        assert_eq!(Str("5".to_owned()), Str("5".to_owned()));
+
+        let set: HashSet<Solution> = [U8(1), Usize(1), I16(-1), Str("1".to_owned())].into();
+        assert_eq!(set.len(), 3);
+    }
+
+    #[test]
+    fn big_integers_compare_by_value() {
+        let max = crate::etc::bigint::BigInt::from(u128::MAX);
+        assert_eq!(BigInt(max.clone()), U128(u128::MAX));
+        assert_eq!(Solution::from(bigint::Int::from(-7)), I8(-7));
+        let beyond = Solution::from(bigint::Int::from(u128::MAX) + bigint::Int::from(1));
+        assert_eq!(
+            beyond.to_string(),
+            "340282366920938463463374607431768211456"
+        );
+        assert_ne!(beyond, U128(u128::MAX));
+        assert_ne!(beyond, U8(0));
+
+        let set: HashSet<Solution> = [BigInt(max), U128(u128::MAX), beyond.clone(), beyond].into();
+        assert_eq!(set.len(), 2);
+    }
+
+    #[test]
+    fn unsolved_and_errors() {
+        assert!(!Unsolved.is_solved());
+        assert_ne!(Unsolved, U8(0));
+        assert_eq!(Unsolved.to_string(), "unsolved");
+        let error = Solution::from(Err::<u64, _>("no path"));
+        assert_eq!(error, Error("no path".to_owned()));
+        assert_eq!(error.to_string(), "error: no path");
+        assert_ne!(error, Str("no path".to_owned()));
+        assert!(!error.is_solved());
+        assert_eq!(Solution::from(Ok::<u64, &str>(3)), U8(3));
+    }
+
+    #[test]
+    fn grids() {
+        let grid = Solution::from(vec![vec![true, false], vec![false, true]]);
+        assert_eq!(grid, Solution::from(vec!["#.".to_owned(), ".#".to_owned()]));
+        assert_eq!(grid.to_string(), "#.\n.#");
+        assert_ne!(grid, Str("#.\n.#".to_owned()));
+    }
+
+    #[test]
+    fn parse_answers() {
+        let parse = |s: &str| s.parse::<Solution>().unwrap();
+        assert!(matches!(parse("42"), U64(42)));
+        assert!(matches!(parse("-42"), I64(-42)));
+        assert!(matches!(
+            parse("340282366920938463463374607431768211455"),
+            U128(u128::MAX)
+        ));
+        assert!(matches!(
+            parse("-170141183460469231731687303715884105728"),
+            I128(i128::MIN)
+        ));
+        assert!(matches!(
+            parse("340282366920938463463374607431768211456"),
+            BigInt(_)
+        ));
+        assert!(matches!(parse("ZRARLFZU"), Str(ref s) if s == "ZRARLFZU"));
+        assert!(matches!(parse(" 42"), Str(_)));
+        let big = "-1".to_owned() + &"0".repeat(40);
+        for solution in [
+            U32(7),
+            I64(-9),
+            U128(1 << 100),
+            parse(&big),
+            Str("CMZ".to_owned()),
+        ] {
+            assert_eq!(parse(&solution.to_string()), solution);
+        }
+    }
+}
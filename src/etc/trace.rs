@@ -0,0 +1,142 @@
+//! Lightweight spans for seeing where a day spends its time, exported in the
+//! Chrome Trace Event format that `chrome://tracing` and Perfetto display.
+//!
+//! Spans are only recorded between `start` and `finish`; otherwise `span` is a
+//! single atomic load, so solvers can keep their spans in hot loops.
+
+use std::borrow::Cow;
+use std::cell::Cell;
+use std::fmt::Write;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Instant;
+
+use crate::etc::json;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static EVENTS: Mutex<Vec<Event>> = Mutex::new(Vec::new());
+static EPOCH: Mutex<Option<Instant>> = Mutex::new(None);
+static NEXT_THREAD: AtomicU64 = AtomicU64::new(1);
+
+thread_local! {
+    static THREAD: Cell<u64> = const { Cell::new(0) };
+}
+
+/// A finished span, with times in microseconds since `start`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Event {
+    pub name: Cow<'static, str>,
+    pub start_us: f64,
+    pub duration_us: f64,
+    pub thread: u64,
+}
+
+/// Guard recording the time until it is dropped as a span.
+#[must_use = "the span ends when the guard is dropped"]
+pub struct Span {
+    name: Cow<'static, str>,
+    start: Option<Instant>,
+}
+
+/// Starts recording spans, discarding any recorded before.
+pub fn start() {
+    EVENTS.lock().unwrap().clear();
+    *EPOCH.lock().unwrap() = Some(Instant::now());
+    ENABLED.store(true, Ordering::Release);
+}
+
+/// Stops recording and returns the spans recorded since `start`.
+pub fn finish() -> Vec<Event> {
+    ENABLED.store(false, Ordering::Release);
+    std::mem::take(&mut *EVENTS.lock().unwrap())
+}
+
+/// Opens a span called `name` that ends when the returned guard is dropped.
+pub fn span(name: impl Into<Cow<'static, str>>) -> Span {
+    let start = ENABLED.load(Ordering::Acquire).then(Instant::now);
+    Span {
+        name: if start.is_some() {
+            name.into()
+        } else {
+            Cow::Borrowed("")
+        },
+        start,
+    }
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        let Some(start) = self.start else {
+            return;
+        };
+        let end = Instant::now();
+        let Some(epoch) = *EPOCH.lock().unwrap() else {
+            return;
+        };
+        let thread = THREAD.with(|thread| {
+            if thread.get() == 0 {
+                thread.set(NEXT_THREAD.fetch_add(1, Ordering::Relaxed));
+            }
+            thread.get()
+        });
+        let micros = |from: Instant, to: Instant| {
+            to.saturating_duration_since(from).as_nanos() as f64 / 1000.0
+        };
+        EVENTS.lock().unwrap().push(Event {
+            name: std::mem::take(&mut self.name),
+            start_us: micros(epoch, start),
+            duration_us: micros(start, end),
+            thread,
+        });
+    }
+}
+
+/// `events` as a Chrome Trace Event JSON document of complete (`X`) events.
+pub fn chrome_json(events: &[Event]) -> String {
+    let mut out = String::from("{\"traceEvents\":[");
+    for (i, event) in events.iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        write!(
+            out,
+            "\n{{\"name\":{},\"cat\":\"aoc\",\"ph\":\"X\",\"ts\":{:.3},\"dur\":{:.3},\"pid\":1,\"tid\":{}}}",
+            json::string(&event.name),
+            event.start_us,
+            event.duration_us,
+            event.thread
+        )
+        .unwrap();
+    }
+    out.push_str("\n],\"displayTimeUnit\":\"ms\"}\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn record_nested_spans() {
+        drop(span("trace test unrecorded"));
+        start();
+        {
+            let _outer = span("trace test outer");
+            let _inner = span(format!("trace test inner {}", 1));
+        }
+        let events: Vec<Event> = finish()
+            .into_iter()
+            .filter(|event| event.name.starts_with("trace test"))
+            .collect();
+        // the span before `start` is not among them
+        assert_eq!(events.len(), 2);
+        let (inner, outer) = (&events[0], &events[1]);
+        assert_eq!(inner.name, "trace test inner 1");
+        assert!(outer.start_us <= inner.start_us);
+        assert!(inner.start_us + inner.duration_us <= outer.start_us + outer.duration_us);
+
+        let json = chrome_json(&events);
+        assert!(json.starts_with("{\"traceEvents\":[\n{\"name\":\"trace test inner 1\",\"cat\":\"aoc\",\"ph\":\"X\",\"ts\":"));
+        assert!(json.ends_with("],\"displayTimeUnit\":\"ms\"}\n"));
+    }
+}
@@ -0,0 +1,116 @@
+//! C ABI for calling the solvers from other languages, declared in `include/aoc.h`.
+
+use std::ffi::{c_char, CString};
+use std::panic;
+use std::ptr;
+use std::slice;
+use std::time::Instant;
+
+use crate::{get_day, Solution};
+
+pub const AOC_OK: i32 = 0;
+pub const AOC_UNKNOWN_DAY: i32 = 1;
+pub const AOC_INVALID_PART: i32 = 2;
+pub const AOC_INVALID_INPUT: i32 = 3;
+pub const AOC_PANIC: i32 = 4;
+pub const AOC_UNSOLVED: i32 = 5;
+
+/// Result of `aoc_solve`. `answer` is set on `AOC_OK`, `error` otherwise, and
+/// both have to be released with `aoc_free_string`.
+#[repr(C)]
+pub struct AocResult {
+    pub status: i32,
+    pub answer: *mut c_char,
+    pub error: *mut c_char,
+    pub elapsed_ms: f64,
+}
+
+impl AocResult {
+    fn error(status: i32, message: &str) -> Self {
+        Self {
+            status,
+            answer: ptr::null_mut(),
+            error: into_c_string(message),
+            elapsed_ms: 0.0,
+        }
+    }
+}
+
+fn into_c_string(s: &str) -> *mut c_char {
+    CString::new(s.replace('\0', ""))
+        .expect("NUL bytes were removed")
+        .into_raw()
+}
+
+/// Solves `part` of `day` for the `len` bytes of UTF-8 input at `input`.
+///
+/// # Safety
+///
+/// `input` must point to `len` readable bytes, or may be null if `len` is 0.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_solve(day: u8, part: u8, input: *const u8, len: usize) -> AocResult {
+    let entry = match get_day(day) {
+        Some(entry) => entry,
+        None => return AocResult::error(AOC_UNKNOWN_DAY, &format!("day {day} is not implemented")),
+    };
+    let solver = match part {
+        1 => entry.part1,
+        2 => entry.part2,
+        _ => return AocResult::error(AOC_INVALID_PART, &format!("not a valid part: {part}")),
+    };
+    let bytes = if input.is_null() || len == 0 {
+        &[]
+    } else {
+        slice::from_raw_parts(input, len)
+    };
+    let input = match std::str::from_utf8(bytes) {
+        Ok(input) => input,
+        Err(e) => return AocResult::error(AOC_INVALID_INPUT, &format!("input is not UTF-8: {e}")),
+    };
+
+    let time = Instant::now();
+    let result = panic::catch_unwind(|| solver(input));
+    let elapsed_ms = time.elapsed().as_nanos() as f64 / 1_000_000.0;
+
+    match result {
+        Ok(solution) => match solution {
+            Solution::Unsolved => AocResult {
+                elapsed_ms,
+                ..AocResult::error(AOC_UNSOLVED, "unsolved")
+            },
+            Solution::Error(message) => AocResult {
+                elapsed_ms,
+                ..AocResult::error(AOC_INVALID_INPUT, &message)
+            },
+            solution => AocResult {
+                status: AOC_OK,
+                answer: into_c_string(&solution.to_string()),
+                error: ptr::null_mut(),
+                elapsed_ms,
+            },
+        },
+        Err(panic) => {
+            let message = panic
+                .downcast_ref::<String>()
+                .map(String::as_str)
+                .or_else(|| panic.downcast_ref::<&str>().copied())
+                .unwrap_or("solver panicked");
+            AocResult {
+                elapsed_ms,
+                ..AocResult::error(AOC_PANIC, message)
+            }
+        }
+    }
+}
+
+/// Releases a string returned in an `AocResult`. Null pointers are ignored.
+///
+/// # Safety
+///
+/// `s` must come from `aoc_solve` and must not be used or freed afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_free_string(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
@@ -0,0 +1,144 @@
+//! Advent of Code 2022 solvers.
+//!
+//! Every day lives in [`days`] with a `solve` function turning the puzzle input
+//! into a [`SolutionPair`], and [`get_day_solver`] maps day numbers to them.
+//! Shared utilities such as the answers log, the AoC client and the HTTP API
+//! live in [`etc`], and [`ffi`] exposes the solvers as a C API.
+
+pub mod days;
+pub mod etc;
+pub mod ffi;
+
+pub use etc::solution::Solution;
+
+use days::{
+    day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11, day12, day13,
+    day14, day15, day16, day17, day18, day19, day20, day21, day22, day23, day24, day25,
+};
+use etc::client::{self, Client};
+use std::fs;
+use std::hint::black_box;
+
+pub type SolutionPair = (Solution, Solution);
+
+/// Reads `input/dayNN.txt`, fetching it first when it is missing.
+pub fn load_input(day: u8) -> Result<String, client::Error> {
+    match fs::read_to_string(format!("input/day{day:02}.txt")) {
+        Ok(input) => Ok(input),
+        Err(_) => Client::from_env()?.fetch_input(day),
+    }
+}
+
+/// Entry points of a day.
+///
+/// `solve` runs both parts, `part1` and `part2` run a single part on the raw
+/// input, and `parse` only parses the input for days with a separate parse step.
+/// Alternative variants of single parts are listed in `variants`, next to the
+/// `default` one made up of `part1` and `part2`. `explain` shows what is behind
+/// the answers for days where they are read off something, like a picture.
+#[derive(Clone, Copy)]
+pub struct Day {
+    pub solve: fn(&str) -> SolutionPair,
+    pub parse: Option<fn(&str)>,
+    pub part1: fn(&str) -> Solution,
+    pub part2: fn(&str) -> Solution,
+    pub variants: &'static [Variant],
+    pub explain: Option<fn(&str) -> Solution>,
+}
+
+/// A named alternative implementation of one part of a day.
+#[derive(Clone, Copy)]
+pub struct Variant {
+    pub name: &'static str,
+    pub part: u8,
+    pub solve: fn(&str) -> Solution,
+}
+
+impl Day {
+    /// All variants of `part`, starting with `default`.
+    pub fn variants(&self, part: u8) -> Vec<Variant> {
+        let default = Variant {
+            name: "default",
+            part,
+            solve: if part == 1 { self.part1 } else { self.part2 },
+        };
+        let others = self.variants.iter().filter(|v| v.part == part).copied();
+        [default].into_iter().chain(others).collect()
+    }
+
+    /// The variant of `part` called `name`.
+    pub fn variant(&self, part: u8, name: &str) -> Option<Variant> {
+        self.variants(part).into_iter().find(|v| v.name == name)
+    }
+}
+
+macro_rules! day {
+    (@parse) => {
+        None
+    };
+    (@parse $parse:expr) => {
+        Some(|input| {
+            black_box($parse(input));
+        })
+    };
+    ($day:ident $(, parse: $parse:expr)?, part1: $part1:expr, part2: $part2:expr) => {
+        Day {
+            solve: $day::solve,
+            parse: day!(@parse $($parse)?),
+            part1: $part1,
+            part2: $part2,
+            variants: &[],
+            explain: None,
+        }
+    };
+    ($day:ident, from_solve) => {
+        day!($day, part1: |input| $day::solve(input).0, part2: |input| $day::solve(input).1)
+    };
+    ($day:ident $(, parse: $parse:expr)?) => {
+        day!(
+            $day $(, parse: $parse)?,
+            part1: |input| Solution::from($day::solve_day1(input)),
+            part2: |input| Solution::from($day::solve_day2(input))
+        )
+    };
+}
+
+/// Returns the entry points of `day`, or `None` if there is no such day.
+#[rustfmt::skip]
+pub fn get_day(day: u8) -> Option<Day> {
+    Some(match day {
+         1 => day!(day01, parse: day01::parse),
+         2 => day!(day02),
+         3 => day!(day03),
+         4 => day!(day04),
+         5 => day!(day05),
+         6 => Day { variants: day06::VARIANTS, ..day!(day06) },
+         7 => day!(day07, parse: day07::parse),
+         8 => day!(day08, parse: day08::parse,
+                   part1: |input| Solution::from(day08::solve_day1(&day08::parse(input))),
+                   part2: |input| Solution::from(day08::solve_day2(&day08::parse(input)))),
+         9 => day!(day09),
+        10 => Day { explain: Some(day10::explain), ..day!(day10) },
+        11 => day!(day11, parse: day11::parse_input),
+        12 => day!(day12, parse: day12::parse),
+        13 => day!(day13),
+        14 => day!(day14, parse: day14::parse),
+        15 => day!(day15, from_solve),
+        16 => day!(day16, from_solve),
+        17 => day!(day17, from_solve),
+        18 => day!(day18, from_solve),
+        19 => day!(day19, from_solve),
+        20 => day!(day20, from_solve),
+        21 => day!(day21, from_solve),
+        22 => day!(day22, from_solve),
+        23 => day!(day23, from_solve),
+        24 => day!(day24, from_solve),
+        25 => day!(day25, from_solve),
+         _ => return None,
+    })
+}
+
+/// Returns the solver of `day`, or `None` if there is no such day.
+pub fn get_day_solver(day: u8) -> Option<fn(&str) -> SolutionPair> {
+    get_day(day).map(|day| day.solve)
+}
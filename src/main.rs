@@ -1,16 +1,16 @@
-mod days;
-mod etc;
-
-use etc::solution::Solution;
-use days::{day01, day02, day03, day04, day05,
-           day06, day07, day08, day09, day10,
-           day11, day12, day13, day14, day15,
-           day16, day17, day18, day19, day20,
-           day21, day22, day23, day24, day25};
-use std::env;
-use std::time::Instant;
-
-pub type SolutionPair = (Solution, Solution);
+use advent_of_code_template::etc::answers::{AnswerLog, Check};
+use advent_of_code_template::etc::client::{unix_now, Client, Verdict};
+use advent_of_code_template::etc::examples::Example;
+use advent_of_code_template::etc::gen;
+use advent_of_code_template::etc::minimize::{self, Checker};
+use advent_of_code_template::etc::progress::Progress;
+use advent_of_code_template::etc::results::{DayResult, PartResult};
+use advent_of_code_template::etc::{server, trace};
+use advent_of_code_template::{get_day, get_day_solver, Solution, SolutionPair};
+use std::net::TcpListener;
+use std::path::Path;
+use std::time::{Duration, Instant};
+use std::{env, fs};
 
 fn main() {
     let args: Vec<String> = env::args().collect();
@@ -18,57 +18,432 @@ fn main() {
         panic!("Please provide the day(s) to run as a command-line argument.");
     }
 
-    let days: Vec<u8> = args[1..].iter()
-        .map(|x| x.parse().unwrap_or_else(|v| panic!("Not a valid day: {}", v)))
+    match args[1].as_str() {
+        "fetch" => fetch(&args[2..]),
+        "submit" => submit(&args[2..]),
+        "examples" => extract_examples(&args[2..]),
+        "serve" => serve(args.get(2).map_or("127.0.0.1:8080", String::as_str)),
+        "compare" => compare(&args[2..]),
+        "gen" => generate(&args[2..]),
+        "minimize" => minimize_input(&args[2..]),
+        "status" => print!(
+            "{}",
+            load_progress().unwrap_or_default().calendar(load_answers)
+        ),
+        _ => run(&args[1..]),
+    }
+}
+
+fn parse_day(arg: &str) -> u8 {
+    arg.parse()
+        .unwrap_or_else(|v| panic!("Not a valid day: {}", v))
+}
+
+/// Positional arguments of `args`, leaving out flags and the values of `options`.
+fn positional<'a>(args: &'a [String], options: &[&str]) -> Vec<&'a str> {
+    let mut positional = Vec::new();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if options.contains(&arg.as_str()) {
+            iter.next();
+        } else if !arg.starts_with("--") {
+            positional.push(arg.as_str());
+        }
+    }
+    positional
+}
+
+/// Value following the option `name` in `args`.
+fn option<'a>(args: &'a [String], name: &str) -> Option<&'a str> {
+    let index = args.iter().position(|x| x == name)?;
+    let value = args
+        .get(index + 1)
+        .unwrap_or_else(|| panic!("{} needs a value", name));
+    Some(value.as_str())
+}
+
+/// Input and expected answers of `day`, from its example when `example_mode` is set.
+fn load_example(day: u8, example_mode: bool) -> Example {
+    if example_mode {
+        Example::load(day).unwrap_or_else(|| panic!("No example for day {:02}", day))
+    } else {
+        Example {
+            part1: load_input(day),
+            part2: None,
+            expected: load_answers(day),
+        }
+    }
+}
+
+/// Runs the given days, on their example inputs when `--example` is passed,
+/// with the parts of `--variant <name>` instead of the default ones, and
+/// writing the spans of the run to `--trace <out.json>`. With `--explain`, also
+/// prints what the answers were read off, and with `--json`, prints the result
+/// of each day as a line of JSON instead.
+fn run(args: &[String]) {
+    let example_mode = args.iter().any(|x| x == "--example");
+    let explain = args.iter().any(|x| x == "--explain");
+    let json = args.iter().any(|x| x == "--json");
+    let variant = option(args, "--variant");
+    let trace_path = option(args, "--trace");
+    let days: Vec<u8> = positional(args, &["--variant", "--trace"])
+        .into_iter()
+        .map(parse_day)
         .collect();
+    if json && cfg!(not(feature = "serde")) {
+        panic!("--json needs the serde feature");
+    }
 
     let mut runtime = 0.0;
+    let (mut solved_parts, mut unsolved_parts, mut failed_parts) = (0, 0, 0);
+    // progress that could not be read is left as it is on disk
+    let loaded_progress = load_progress();
+    let saved_progress = loaded_progress.as_ref().map(Progress::to_string);
+    let mut progress = loaded_progress.unwrap_or_default();
+    if trace_path.is_some() {
+        trace::start();
+    }
 
     for day in days {
-        let func = get_day_solver(day);
+        let entry = get_day(day).unwrap_or_else(|| panic!("Day {} is not implemented", day));
+        let example = load_example(day, example_mode);
+        let name = variant.unwrap_or("default");
+        let parts = [1, 2].map(|part| {
+            entry.variant(part, name).unwrap_or_else(|| {
+                panic!("Day {:02} has no variant {} of part {}", day, name, part)
+            })
+        });
 
-        let time = Instant::now();
-        let (p1, p2) = func();
-        let elapsed_ms = time.elapsed().as_nanos() as f64 / 1_000_000.0;
-        
-        println!("\n=== Day {:02} ===", day);
-        println!("  · Part 1: {}", p1);
-        println!("  · Part 2: {}", p2);
-        println!("  · Elapsed: {:.4} ms", elapsed_ms);
+        let day_span = trace::span(format!("day {:02}", day));
+        let solved = parts.map(|part| {
+            let _span = trace::span(format!("part {}", part.part));
+            let time = Instant::now();
+            let solution = (part.solve)(example.input(part.part));
+            (solution, time.elapsed().as_nanos() as f64 / 1_000_000.0)
+        });
+        drop(day_span);
+
+        let answers = &example.expected;
+        let checks = [1, 2].map(|part| answers.check(part, &solved[part as usize - 1].0));
+        let result = DayResult {
+            day,
+            example: example_mode,
+            parts: solved
+                .into_iter()
+                .zip(&checks)
+                .enumerate()
+                .map(|(i, ((answer, elapsed_ms), check))| PartResult {
+                    part: i as u8 + 1,
+                    variant: name.to_owned(),
+                    answer,
+                    elapsed_ms,
+                    verified: *check == Check::Correct,
+                })
+                .collect(),
+        };
+        let elapsed_ms = result.elapsed_ms();
+        if !example_mode && variant.is_none() {
+            let day_progress = progress.day_mut(day);
+            day_progress.runtime_ms = Some(elapsed_ms);
+            day_progress.verified = [result.parts[0].verified, result.parts[1].verified];
+        }
+
+        if json {
+            print_json(&result);
+        } else {
+            println!("\n=== Day {:02} ===", day);
+            for (part, check) in result.parts.iter().zip(checks) {
+                print_part(part.part, &part.answer, check);
+            }
+            if let Some(explanation) = entry.explain.filter(|_| explain) {
+                match explanation(example.input(2)) {
+                    grid @ Solution::Grid(_) => println!("  · Explanation:{}", indented(&grid, 6)),
+                    text => println!("  · Explanation: {}", text),
+                }
+            }
+            println!("  · Elapsed: {:.4} ms", elapsed_ms);
+        }
 
         runtime += elapsed_ms;
+        for part in &result.parts {
+            match part.answer {
+                Solution::Unsolved => unsolved_parts += 1,
+                Solution::Error(_) => failed_parts += 1,
+                _ => solved_parts += 1,
+            }
+        }
+    }
+
+    if !json {
+        println!("Total runtime: {:.4} ms", runtime);
+        println!(
+            "Parts: {} solved, {} unsolved, {} failed",
+            solved_parts, unsolved_parts, failed_parts
+        );
+    }
+
+    if let Some(path) = trace_path {
+        fs::write(path, trace::chrome_json(&trace::finish()))
+            .unwrap_or_else(|e| panic!("Could not write {}: {}", path, e));
+        if !json {
+            println!("Trace written to {}", path);
+        }
+    }
+
+    if saved_progress.is_some_and(|saved| progress.to_string() != saved) {
+        progress
+            .save()
+            .unwrap_or_else(|e| panic!("Could not save progress: {}", e));
+    }
+}
+
+#[cfg(feature = "serde")]
+fn print_json(result: &DayResult) {
+    println!("{}", serde_json::to_string(result).unwrap());
+}
+
+#[cfg(not(feature = "serde"))]
+fn print_json(_: &DayResult) {
+    unreachable!("--json needs the serde feature")
+}
+
+/// `compare <days...> [--example]`, running every variant of each part on the
+/// same input and tabulating their answers and best time out of `COMPARE_RUNS`.
+fn compare(args: &[String]) {
+    const COMPARE_RUNS: usize = 5;
+    let example_mode = args.iter().any(|x| x == "--example");
+
+    for day in positional(args, &[]).into_iter().map(parse_day) {
+        let entry = get_day(day).unwrap_or_else(|| panic!("Day {} is not implemented", day));
+        let example = load_example(day, example_mode);
+
+        println!("\n=== Day {:02} ===", day);
+        println!(
+            "  {:<4}  {:<12}  {:>12}  Answer",
+            "Part", "Variant", "Time (ms)"
+        );
+        for part in 1..=2 {
+            let mut answers = Vec::new();
+            for variant in entry.variants(part) {
+                let mut best_ms = f64::INFINITY;
+                let mut answer = Solution::from("");
+                for _ in 0..COMPARE_RUNS {
+                    let time = Instant::now();
+                    answer = (variant.solve)(example.input(part));
+                    best_ms = best_ms.min(time.elapsed().as_nanos() as f64 / 1_000_000.0);
+                }
+                println!(
+                    "  {:<4}  {:<12}  {:>12.4}  {}",
+                    part,
+                    variant.name,
+                    best_ms,
+                    indented(&answer, 36)
+                );
+                answers.push(answer);
+            }
+            if answers.windows(2).any(|pair| pair[0] != pair[1]) {
+                println!("  · Part {}: (!) variants disagree", part);
+            }
+        }
     }
+}
+
+/// `gen <day> [--seed S] [--size N]`, printing a random input of `day`.
+fn generate(args: &[String]) {
+    let day = match positional(args, &["--seed", "--size"])[..] {
+        [day] => parse_day(day),
+        _ => panic!("Usage: gen <day> [--seed S] [--size N]"),
+    };
+    let number = |name, default| {
+        option(args, name).map_or(default, |value| {
+            value
+                .parse()
+                .unwrap_or_else(|_| panic!("{} needs a number, got {}", name, value))
+        })
+    };
+    let input = gen::generate(day, number("--seed", 0), number("--size", 100) as usize)
+        .unwrap_or_else(|| panic!("No input generator for day {:02}", day));
+    print!("{}", input);
+}
+
+/// `minimize <day> <input> [--timeout SECONDS]`, shrinking an input on which a
+/// part panics, times out or disagrees with a variant or the reference script.
+fn minimize_input(args: &[String]) {
+    let (day, path) = match positional(args, &["--timeout"])[..] {
+        [day, path] => (parse_day(day), path),
+        _ => panic!("Usage: minimize <day> <input> [--timeout SECONDS]"),
+    };
+    let timeout = option(args, "--timeout").map_or(10.0, |value| {
+        value
+            .parse()
+            .unwrap_or_else(|_| panic!("--timeout needs a number, got {}", value))
+    });
+    let checker = Checker::new(day, Duration::from_secs_f64(timeout))
+        .unwrap_or_else(|| panic!("Day {} is not implemented", day));
+    let input =
+        fs::read_to_string(path).unwrap_or_else(|e| panic!("Could not read {}: {}", path, e));
+
+    let (failure, minimized) = minimize::quiet_panics(|| {
+        let failure = checker.detect(&input)?;
+        println!(
+            "Day {:02} {}, minimising {} lines",
+            day,
+            failure,
+            input.lines().count()
+        );
+        let minimized =
+            minimize::minimize(&input, |candidate| checker.reproduces(&failure, candidate));
+        Some((failure, minimized))
+    })
+    .unwrap_or_else(|| panic!("Day {:02} does not fail on {}", day, path));
+
+    let out = format!("{}.min", path);
+    fs::write(&out, &minimized).unwrap_or_else(|e| panic!("Could not write {}: {}", out, e));
+    println!("Minimised input written to {}, as a test case:\n", out);
+    print!("{}", checker.test_case(&failure, &minimized));
+}
+
+/// Progress of all days, or `None` after reporting why it could not be read.
+fn load_progress() -> Option<Progress> {
+    Progress::load()
+        .map_err(|e| eprintln!("(!) Ignoring progress: {}", e))
+        .ok()
+}
+
+/// Answers log of `day`, or an empty one after reporting why it could not be read.
+fn load_answers(day: u8) -> AnswerLog {
+    AnswerLog::load(day).unwrap_or_else(|e| {
+        eprintln!("(!) Ignoring answers of day {:02}: {}", day, e);
+        AnswerLog::default()
+    })
+}
+
+fn load_input(day: u8) -> String {
+    if !Path::new(&format!("input/day{:02}.txt", day)).exists() {
+        println!("Fetching missing input for day {:02}", day);
+    }
+    advent_of_code_template::load_input(day)
+        .unwrap_or_else(|e| panic!("Could not load input of day {:02}: {}", day, e))
+}
+
+fn fetch(args: &[String]) {
+    let client = Client::from_env().unwrap_or_else(|e| panic!("{}", e));
+    for day in args.iter().map(|x| parse_day(x)) {
+        match client.fetch_input(day) {
+            Ok(_) => println!("Day {:02}: {}", day, client.input_path(day).display()),
+            Err(e) => println!("Day {:02}: {}", day, e),
+        }
+    }
+}
+
+fn serve(addr: &str) {
+    let listener =
+        TcpListener::bind(addr).unwrap_or_else(|e| panic!("Could not bind {}: {}", addr, e));
+    println!("Serving solvers on http://{}", addr);
+    server::serve(listener, get_day).unwrap_or_else(|e| panic!("Server failed: {}", e));
+}
+
+/// `examples <day> <page.html>`, extracting the examples of a saved puzzle page.
+fn extract_examples(args: &[String]) {
+    let (day, path) = match args {
+        [day, path] => (parse_day(day), path),
+        _ => panic!("Usage: examples <day> <page.html>"),
+    };
+    let page =
+        fs::read_to_string(path).unwrap_or_else(|e| panic!("Could not read {}: {}", path, e));
+    let example = Example::extract(&page).unwrap_or_else(|| panic!("No example in {}", path));
+    example
+        .save(day)
+        .unwrap_or_else(|e| panic!("Could not save example: {}", e));
+    print!(
+        "Day {:02} example saved, expecting:\n{}",
+        day, example.expected
+    );
+}
+
+/// `submit <day> <part> [answer]`, submitting the solver's answer when none is given.
+fn submit(args: &[String]) {
+    let (day, part) = match args {
+        [day, part, ..] if part == "1" || part == "2" => {
+            (parse_day(day), part.parse::<u8>().unwrap())
+        }
+        _ => panic!("Usage: submit <day> <1|2> [answer]"),
+    };
+    let answer = match args.get(2) {
+        Some(answer) => answer.parse().unwrap(),
+        None => {
+            let (p1, p2) = solver(day)(&load_input(day));
+            if part == 1 {
+                p1
+            } else {
+                p2
+            }
+        }
+    };
+
+    if !answer.is_solved() {
+        println!("Not submitting day {:02} part {}: {}", day, part, answer);
+        return;
+    }
+    // submitting needs the rejected answers, so a log that can't be read stops it
+    let mut answers = AnswerLog::load(day).unwrap_or_else(|e| panic!("{}", e));
+    match answers.check(part, &answer) {
+        Check::Unknown | Check::Plausible => {}
+        check => {
+            println!("Not submitting {}: {}", answer, check);
+            return;
+        }
+    }
+
+    let client = Client::from_env().unwrap_or_else(|e| panic!("{}", e));
+    let verdict = client
+        .submit(day, part, &answer.to_string())
+        .unwrap_or_else(|e| panic!("Could not submit: {}", e));
+    println!("Day {:02} part {}: {} is {}", day, part, answer, verdict);
+
+    match verdict {
+        Verdict::Correct => {
+            answers.accept(part, &answer.to_string());
+            if let Some(mut progress) = load_progress() {
+                progress.day_mut(day).solved_at[part as usize - 1] = Some(unix_now());
+                progress
+                    .save()
+                    .unwrap_or_else(|e| panic!("Could not save progress: {}", e));
+            }
+        }
+        Verdict::Incorrect(hint) => answers.reject(part, &answer.to_string(), hint),
+        Verdict::Wait(_) | Verdict::AlreadySolved => return,
+    }
+    answers
+        .save(day)
+        .unwrap_or_else(|e| panic!("Could not save answers: {}", e));
+}
+
+fn print_part(part: u8, solution: &Solution, check: Check) {
+    let check = match check {
+        Check::Unknown | Check::Plausible => String::new(),
+        check => format!(" {}", check),
+    };
+    match solution {
+        Solution::Grid(_) => println!("  · Part {}:{}{}", part, check, indented(solution, 6)),
+        Solution::Unsolved => println!("  · Part {}: (unsolved)", part),
+        Solution::Error(message) => println!("  · Part {}: (!) failed: {}", part, message),
+        _ => println!("  · Part {}: {}{}", part, solution, check),
+    }
+}
 
-    println!("Total runtime: {:.4} ms", runtime);
-}
-
-fn get_day_solver(day: u8) -> fn() -> SolutionPair {
-    match day {
-         1 => day01::solve,
-         2 => day02::solve,
-         3 => day03::solve,
-         4 => day04::solve,
-         5 => day05::solve,
-         6 => day06::solve,
-         7 => day07::solve,
-         8 => day08::solve,
-         9 => day09::solve,
-        10 => day10::solve,
-        11 => day11::solve,
-        12 => day12::solve,
-        13 => day13::solve,
-        14 => day14::solve,
-        15 => day15::solve,
-        16 => day16::solve,
-        17 => day17::solve,
-        18 => day18::solve,
-        19 => day19::solve,
-        20 => day20::solve,
-        21 => day21::solve,
-        22 => day22::solve,
-        23 => day23::solve,
-        24 => day24::solve,
-        25 => day25::solve,
-         _ => unimplemented!(),
-    }
-}
\ No newline at end of file
+/// `solution` as text, with the rows of a grid each on a line of their own
+/// indented by `indent` spaces.
+fn indented(solution: &Solution, indent: usize) -> String {
+    match solution {
+        Solution::Grid(rows) => rows
+            .iter()
+            .map(|row| format!("\n{:indent$}{}", "", row, indent = indent))
+            .collect(),
+        _ => solution.to_string(),
+    }
+}
+
+fn solver(day: u8) -> fn(&str) -> SolutionPair {
+    get_day_solver(day).unwrap_or_else(|| panic!("Day {} is not implemented", day))
+}
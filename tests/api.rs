@@ -0,0 +1,59 @@
+use advent_of_code_template::days::day13::Packet;
+use advent_of_code_template::days::{day01, day08};
+use advent_of_code_template::etc::examples::Example;
+use advent_of_code_template::{get_day, get_day_solver, Solution};
+
+#[test]
+fn registry_runs_day_modules() {
+    let input = "1000\n2000\n\n4000\n";
+    let (p1, p2) = get_day_solver(1).unwrap()(input);
+    assert_eq!(p1, Solution::from(day01::solve_day1(input)));
+    assert_eq!(p2, Solution::from(day01::solve_day2(input)));
+    assert!(get_day_solver(26).is_none());
+}
+
+#[test]
+fn parsers_are_reachable() {
+    let (packet, trailing) = Packet::parse_one("[1,[2,3]]");
+    assert_eq!(trailing, "");
+    assert!(packet < Packet::parse_one("[1,[4]]").0);
+
+    let map = day08::parse("303\n255\n653\n");
+    assert_eq!(day08::solve_day1(&map), 9);
+}
+
+#[test]
+fn parts_agree_with_solve() {
+    for number in 1..=14 {
+        let day = get_day(number).unwrap();
+        let example = Example::load(number).unwrap();
+        let (p1, _) = (day.solve)(&example.part1);
+        assert_eq!((day.part1)(&example.part1), p1);
+        let part2 = example.input(2);
+        let (_, p2) = (day.solve)(part2);
+        assert_eq!((day.part2)(part2), p2);
+        if let Some(parse) = day.parse {
+            parse(&example.part1);
+        }
+    }
+}
+
+#[test]
+fn variants_agree_with_default() {
+    for day in 1..=25 {
+        let (Some(entry), Some(example)) = (get_day(day), Example::load(day)) else {
+            continue;
+        };
+        for part in 1..=2 {
+            let expected = entry.variants(part)[0].solve;
+            for variant in entry.variants(part) {
+                assert_eq!(
+                    (variant.solve)(example.input(part)),
+                    expected(example.input(part)),
+                    "day {day} part {part} variant {}",
+                    variant.name
+                );
+            }
+        }
+    }
+}
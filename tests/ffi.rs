@@ -0,0 +1,74 @@
+use std::env;
+use std::ffi::{c_char, CStr};
+
+use advent_of_code_template::ffi::{
+    AocResult, AOC_INVALID_INPUT, AOC_OK, AOC_PANIC, AOC_UNKNOWN_DAY, AOC_UNSOLVED,
+};
+use libloading::{library_filename, Library, Symbol};
+
+type Solve = unsafe extern "C" fn(u8, u8, *const u8, usize) -> AocResult;
+type FreeString = unsafe extern "C" fn(*mut c_char);
+
+/// Loads the cdylib cargo built alongside this test in `target/<profile>/deps`.
+fn load() -> Library {
+    let exe = env::current_exe().unwrap();
+    let path = exe.with_file_name(library_filename("advent_of_code_template"));
+    unsafe { Library::new(path).unwrap() }
+}
+
+fn take(free: &FreeString, s: *mut c_char) -> Option<String> {
+    if s.is_null() {
+        return None;
+    }
+    let owned = unsafe { CStr::from_ptr(s) }.to_str().unwrap().to_owned();
+    unsafe { free(s) };
+    Some(owned)
+}
+
+fn call(lib: &Library, day: u8, part: u8, input: &[u8]) -> (i32, Option<String>, Option<String>) {
+    unsafe {
+        let solve: Symbol<Solve> = lib.get(b"aoc_solve").unwrap();
+        let free: Symbol<FreeString> = lib.get(b"aoc_free_string").unwrap();
+        let result = solve(day, part, input.as_ptr(), input.len());
+        (
+            result.status,
+            take(&free, result.answer),
+            take(&free, result.error),
+        )
+    }
+}
+
+#[test]
+fn solve_through_c_abi() {
+    let lib = load();
+    let input = b"2-4,6-8\n2-3,4-5\n5-7,7-9\n2-8,3-7\n6-6,4-6\n2-6,4-8\n";
+    assert_eq!(
+        call(&lib, 4, 1, input),
+        (AOC_OK, Some("2".to_owned()), None)
+    );
+    assert_eq!(
+        call(&lib, 4, 2, input),
+        (AOC_OK, Some("4".to_owned()), None)
+    );
+}
+
+#[test]
+fn report_errors_through_c_abi() {
+    let lib = load();
+    let (status, answer, error) = call(&lib, 26, 1, b"");
+    assert_eq!((status, answer), (AOC_UNKNOWN_DAY, None));
+    assert!(error.is_some());
+
+    assert_eq!(call(&lib, 4, 1, &[0xff, 0xfe]).0, AOC_INVALID_INPUT);
+    assert_eq!(call(&lib, 4, 1, b"nonsense\n").0, AOC_PANIC);
+    assert_eq!(call(&lib, 12, 1, b"SbcE\n").0, AOC_INVALID_INPUT);
+    assert_eq!(call(&lib, 25, 1, b"").0, AOC_UNSOLVED);
+}
+
+#[test]
+fn solve_only_the_requested_part() {
+    let lib = load();
+    // day 6 part 2 panics on a buffer this short, part 1 answers
+    assert_eq!(call(&lib, 6, 1, b"abc\n").0, AOC_OK);
+    assert_eq!(call(&lib, 6, 2, b"abc\n").0, AOC_PANIC);
+}
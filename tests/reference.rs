@@ -0,0 +1,39 @@
+use advent_of_code_template::etc::gen;
+use advent_of_code_template::etc::reference::Reference;
+use advent_of_code_template::get_day_solver;
+
+#[test]
+fn rust_agrees_with_reference_scripts() {
+    if !Reference::python_available() {
+        eprintln!("No python interpreter, skipping the reference scripts");
+        return;
+    }
+
+    let mut failures = Vec::new();
+    for day in 1..=25 {
+        let Some(reference) = Reference::find(day) else {
+            continue;
+        };
+        let solver = get_day_solver(day).unwrap();
+
+        let mut inputs = Vec::new();
+        if let Ok(input) = std::fs::read_to_string(format!("input/day{day:02}.txt")) {
+            inputs.push(("real input".to_owned(), input));
+        }
+        for seed in 1..=20 {
+            if let Some(input) = gen::generate(day, seed, 50) {
+                inputs.push((format!("generated input (seed {seed})"), input));
+            }
+        }
+
+        for (name, input) in inputs {
+            let disagreements = reference
+                .compare(solver, &input)
+                .unwrap_or_else(|e| panic!("day {day}, {name}: {e}"));
+            for disagreement in disagreements {
+                failures.push(format!("day {day}, {name}: {disagreement}"));
+            }
+        }
+    }
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}